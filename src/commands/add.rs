@@ -1,10 +1,11 @@
-use crate::templates;
+use crate::hooks::{self, HookEvent, HooksConfig};
+use crate::manifest::{PathOrInline, PluginManifest};
+use crate::mcp::{self, McpConfig, McpServer, Transport};
+use crate::templates::Templates;
 use crate::utils;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::Subcommand;
 use colored::*;
-use convert_case::{Case, Casing};
-use dialoguer::Input;
 
 #[derive(Subcommand)]
 pub enum Component {
@@ -13,6 +14,9 @@ pub enum Component {
         name: Option<String>,
         #[arg(short, long)]
         description: Option<String>,
+        /// Write the legacy flat skills/<name>.md instead of skills/<name>/SKILL.md
+        #[arg(long)]
+        flat: bool,
     },
     Command {
         #[arg(short, long)]
@@ -29,8 +33,44 @@ pub enum Component {
         description: Option<String>,
     },
     Hook {
+        /// Lifecycle event to hook into (e.g. PreToolUse, SessionStart)
+        #[arg(short, long, value_enum)]
+        event: HookEvent,
+        /// Tool name pattern to match, e.g. "Write|Edit" for PreToolUse
         #[arg(short, long)]
-        event: String,
+        matcher: Option<String>,
+        /// Seconds before the hook is cancelled
+        #[arg(short, long)]
+        timeout: Option<u64>,
+        /// Script name (defaults to the event, e.g. pre-tool-use.sh)
+        #[arg(short, long)]
+        name: Option<String>,
+    },
+    /// MCP server, merged into the plugin's .mcp.json
+    Mcp {
+        #[arg(short, long)]
+        name: Option<String>,
+        /// Transport (defaults to http with --url, stdio otherwise)
+        #[arg(short, long, value_enum)]
+        transport: Option<Transport>,
+        /// Executable for stdio servers; ./paths are anchored to ${CLAUDE_PLUGIN_ROOT}
+        #[arg(short, long, conflicts_with = "url")]
+        command: Option<String>,
+        /// Argument for the command (repeatable)
+        #[arg(short, long = "arg", allow_hyphen_values = true)]
+        args: Vec<String>,
+        /// Environment variable KEY=VALUE (repeatable)
+        #[arg(short, long, value_parser = parse_pair)]
+        env: Vec<(String, String)>,
+        /// Endpoint for http/sse servers
+        #[arg(short, long)]
+        url: Option<String>,
+        /// HTTP header NAME=VALUE (repeatable)
+        #[arg(long = "header", value_parser = parse_pair)]
+        headers: Vec<(String, String)>,
+        /// Also point plugin.json's mcpServers at .mcp.json
+        #[arg(long)]
+        manifest: bool,
     },
 }
 
@@ -40,21 +80,34 @@ pub fn run(plugin_flag: Option<String>, component: Component) -> Result<()> {
     let plugin_name = root.file_name().unwrap().to_string_lossy();
     println!("🔧 Targeting plugin: {}", plugin_name.cyan());
 
+    let templates = Templates::discover(&root);
     match component {
-        Component::Skill { name, description } => {
-            let n = get_name(name, "Skill Name")?;
-            let desc = get_description(description)?;
-            let filename = format!("{}.md", n.to_case(Case::Kebab));
+        Component::Skill {
+            name,
+            description,
+            flat,
+        } => {
+            let n = utils::prompt(name, "Skill Name", "--name")?;
+            let desc = utils::get_description(description)?;
+            let slug = utils::slug(&n)?;
 
-            let content = templates::get_skill_template(&n, &desc);
-            utils::create_file(&root.join("skills").join(filename), &content)?;
+            if flat {
+                let content = templates.legacy_skill(&n, &slug, &desc)?;
+                utils::create_file(&root.join("skills").join(format!("{slug}.md")), &content)?;
+            } else {
+                let skill_dir = root.join("skills").join(&slug);
+                utils::create_dir(&skill_dir)?;
+                let content = templates.skill(&n, &slug, &desc)?;
+                utils::create_file(&skill_dir.join("SKILL.md"), &content)?;
+            }
         }
         Component::Command { name, description } => {
-            let n = get_name(name, "Command Name")?;
-            let desc = get_description(description)?;
-            let filename = format!("{}.md", n.to_case(Case::Kebab));
+            let n = utils::prompt(name, "Command Name", "--name")?;
+            let desc = utils::get_description(description)?;
+            let slug = utils::slug(&n)?;
+            let filename = format!("{slug}.md");
 
-            let content = templates::get_command_template(&n, &desc);
+            let content = templates.command(&n, &slug, &desc)?;
             utils::create_file(&root.join("commands").join(filename), &content)?;
         }
         Component::Agent {
@@ -62,49 +115,147 @@ pub fn run(plugin_flag: Option<String>, component: Component) -> Result<()> {
             capabilities,
             description,
         } => {
-            let n = get_name(name, "Agent Name")?;
-            let desc = get_description(description)?;
+            let n = utils::prompt(name, "Agent Name", "--name")?;
+            let desc = utils::get_description(description)?;
             let caps = capabilities.unwrap_or_else(|| "bash, search".to_string());
-            let filename = format!("{}.md", n.to_case(Case::Kebab));
+            let slug = utils::slug(&n)?;
+            let filename = format!("{slug}.md");
 
-            let content = templates::get_agent_template(&n, &desc, &caps);
+            let content = templates.agent(&n, &slug, &desc, &caps)?;
             utils::create_file(&root.join("agents").join(filename), &content)?;
         }
-        Component::Hook { event } => {
-            let filename = match event.as_str() {
-                "start" => "on_session_start.sh",
-                "message" => "on_user_message.sh",
-                _ => return Err(anyhow::anyhow!("Unknown event. Use 'start' or 'message'")),
+        Component::Hook {
+            event,
+            matcher,
+            timeout,
+            name,
+        } => {
+            // Names are slugged like every other component. The template is a
+            // bash script, so other extensions are refused rather than mislabelled
+            let filename = match name {
+                Some(n) => match n.rsplit_once('.') {
+                    Some((stem, "sh")) => format!("{}.sh", utils::slug(stem)?),
+                    Some((_, ext)) => {
+                        return Err(anyhow!(
+                            "No hook template for .{ext} scripts; use a .sh name, or write the script yourself and wire it into hooks/hooks.json"
+                        ))
+                    }
+                    None => format!("{}.sh", utils::slug(&n)?),
+                },
+                None => event.script_name(),
             };
 
-            let path = root.join("hooks").join(filename);
-            let content = "#!/bin/bash\necho 'Hook triggered'";
-            utils::create_file(&path, content)?;
+            let path = root.join("hooks").join(&filename);
+            utils::create_dir(&root.join("hooks"))?;
+            let created = !path.exists();
+            let content = templates.hook_script(event.name())?;
+            utils::create_file(&path, &content)?;
 
-            // Make executable (Unix only)
+            // Make executable (Unix only); an existing script keeps its mode
             #[cfg(unix)]
-            {
+            if created {
                 use std::os::unix::fs::PermissionsExt;
                 let mut perms = std::fs::metadata(&path)?.permissions();
                 perms.set_mode(0o755);
                 std::fs::set_permissions(&path, perms)?;
                 println!("{} marked as executable (+x)", filename.green());
             }
+            #[cfg(not(unix))]
+            let _ = created;
+
+            // Wire the script into hooks/hooks.json
+            let command = format!("{}/hooks/{filename}", hooks::PLUGIN_ROOT_VAR);
+            let mut config = HooksConfig::load_or_default(&root)?;
+            if config.add_command(event, matcher, command, timeout)? {
+                config.save(&root)?;
+                println!(
+                    "{} {} in hooks/hooks.json",
+                    "Registered:".green(),
+                    event.name()
+                );
+            } else {
+                println!(
+                    "{} {} already runs {filename}",
+                    "Skipped (exists):".yellow(),
+                    event.name()
+                );
+            }
         }
-    }
-    Ok(())
-}
+        Component::Mcp {
+            name,
+            transport,
+            command,
+            args,
+            env,
+            url,
+            headers,
+            manifest,
+        } => {
+            let n = utils::slug(&utils::prompt(name, "Server Name", "--name")?)?;
+            let transport = transport.unwrap_or(match url {
+                Some(_) => Transport::Http,
+                None => Transport::Stdio,
+            });
+
+            let server = match transport {
+                Transport::Stdio => {
+                    let command = command
+                        .ok_or_else(|| anyhow!("stdio servers need --command <executable>"))?;
+                    McpServer {
+                        command: Some(mcp::anchor_to_plugin_root(&command)),
+                        args: args.iter().map(|a| mcp::anchor_to_plugin_root(a)).collect(),
+                        env: env.into_iter().collect(),
+                        ..Default::default()
+                    }
+                }
+                Transport::Http | Transport::Sse => {
+                    if !args.is_empty() || !env.is_empty() {
+                        return Err(anyhow!("--arg and --env only apply to stdio servers"));
+                    }
+                    let url = url.ok_or_else(|| {
+                        anyhow!("{} servers need --url <endpoint>", transport.name())
+                    })?;
+                    McpServer {
+                        transport: Some(transport.name().to_string()),
+                        url: Some(url),
+                        headers: headers.into_iter().collect(),
+                        ..Default::default()
+                    }
+                }
+            };
+
+            let mut config = McpConfig::load_or_default(&root)?;
+            if config.add_server(&n, &server)? {
+                config.save(&root)?;
+                println!("{} MCP server '{n}' in .mcp.json", "Registered:".green());
+            } else {
+                println!(
+                    "{} MCP server '{n}' already exists in .mcp.json",
+                    "Skipped (exists):".yellow()
+                );
+            }
 
-fn get_name(arg: Option<String>, prompt: &str) -> Result<String> {
-    match arg {
-        Some(n) => Ok(n),
-        None => Ok(Input::new().with_prompt(prompt).interact_text()?),
+            if manifest {
+                let manifest_path = root.join(".claude-plugin/plugin.json");
+                let mut plugin = PluginManifest::load(&manifest_path)?;
+                if plugin.mcp_servers.is_none() {
+                    plugin.mcp_servers = Some(PathOrInline::Path(format!("./{}", McpConfig::FILE)));
+                    plugin.save(&manifest_path)?;
+                    println!("{} mcpServers in plugin.json", "Updated:".green());
+                } else {
+                    println!(
+                        "{} plugin.json already declares mcpServers",
+                        "Skipped (exists):".yellow()
+                    );
+                }
+            }
+        }
     }
+    Ok(())
 }
 
-fn get_description(arg: Option<String>) -> Result<String> {
-    match arg {
-        Some(d) => Ok(d),
-        None => Ok(Input::new().with_prompt("Description").interact_text()?),
-    }
+fn parse_pair(s: &str) -> Result<(String, String), String> {
+    s.split_once('=')
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .ok_or_else(|| format!("expected NAME=VALUE, got \"{s}\""))
 }
@@ -0,0 +1,351 @@
+use crate::json;
+use crate::manifest::{Marketplace, PluginManifest};
+use crate::utils;
+use anyhow::{anyhow, Context, Result};
+use semver::Version;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::str::FromStr;
+
+/// How far to move the version: a semver level or an explicit release.
+#[derive(Clone, Debug)]
+pub enum Level {
+    Major,
+    Minor,
+    Patch,
+    Exact(Version),
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "major" => Ok(Level::Major),
+            "minor" => Ok(Level::Minor),
+            "patch" => Ok(Level::Patch),
+            _ => Version::parse(s)
+                .map(Level::Exact)
+                .map_err(|_| format!("expected major, minor, patch or x.y.z, got \"{s}\"")),
+        }
+    }
+}
+
+impl Level {
+    fn apply(&self, current: &Version) -> Version {
+        match self {
+            Level::Major => Version::new(current.major + 1, 0, 0),
+            Level::Minor => Version::new(current.major, current.minor + 1, 0),
+            Level::Patch => Version::new(current.major, current.minor, current.patch + 1),
+            Level::Exact(v) => v.clone(),
+        }
+    }
+}
+
+pub fn run(plugin: String, level: Level, from_git: bool) -> Result<()> {
+    let root = utils::resolve_plugin_root(&Some(plugin))?;
+    let manifest_path = root.join(".claude-plugin/plugin.json");
+    let mut manifest = PluginManifest::load(&manifest_path)?;
+
+    let current = manifest.meta.version.as_deref().unwrap_or("0.0.0");
+    let current = Version::parse(current)
+        .with_context(|| format!("plugin.json version \"{current}\" is not semver"))?;
+    let next = level.apply(&current);
+    if next <= current {
+        return Err(anyhow!("New version {next} must be greater than {current}"));
+    }
+
+    // Gather commit subjects before CHANGELOG.md changes, since it marks the last release
+    let entries = match from_git {
+        true => git_entries(&root)?,
+        false => Vec::new(),
+    };
+
+    // Read everything before writing anything, so a broken file leaves no
+    // half-applied bump behind
+    let market_path = utils::find_marketplace_root(&root)
+        .map(|market_root| market_root.join(".claude-plugin/marketplace.json"));
+    let marketplace = match &market_path {
+        Some(path) => Some(Marketplace::load(path)?),
+        None => None,
+    };
+    let changelog_path = root.join("CHANGELOG.md");
+    let changelog = match fs::read_to_string(&changelog_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => keep_a_changelog_header(),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {changelog_path:?}")),
+    };
+    let changelog = insert_release(&changelog, &next, &utils::today(), &entries);
+
+    manifest.meta.version = Some(next.to_string());
+    manifest.save(&manifest_path)?;
+    println!("📦 {}: {current} → {next}", manifest.name);
+    println!("Updated: {:?}", manifest_path);
+
+    if let (Some(market_path), Some(mut marketplace)) = (market_path, marketplace) {
+        match marketplace.find_mut(&manifest.name) {
+            Some(entry) => {
+                entry.meta.version = Some(next.to_string());
+                marketplace.save(&market_path)?;
+                println!("Updated: {:?}", market_path);
+            }
+            None => println!(
+                "⚠️  '{}' is not registered in marketplace.json; run `cforge register` to add it.",
+                manifest.name
+            ),
+        }
+    }
+
+    json::write_atomic(&changelog_path, &changelog)?;
+    println!("Updated: {:?}", changelog_path);
+    Ok(())
+}
+
+fn keep_a_changelog_header() -> String {
+    r#"# Changelog
+
+All notable changes to this plugin will be documented in this file.
+
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),
+and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).
+
+## [Unreleased]
+"#
+    .to_string()
+}
+
+// `(section, entry)` pairs from commit subjects touching the plugin since the
+// last commit that edited its CHANGELOG.md (i.e. the previous release)
+fn git_entries(root: &Path) -> Result<Vec<(&'static str, String)>> {
+    let git = |args: &[&str]| -> Result<String> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .output()
+            .context("Failed to run git")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+
+    let last_release = git(&["log", "-1", "--format=%H", "--", "CHANGELOG.md"])?;
+    let range = match last_release.trim() {
+        "" => "HEAD".to_string(),
+        hash => format!("{hash}..HEAD"),
+    };
+    let subjects = git(&["log", "--no-merges", "--format=%s", &range, "--", "."])?;
+
+    Ok(subjects
+        .lines()
+        .filter(|s| !s.trim().is_empty())
+        .map(classify)
+        .collect())
+}
+
+// Conventional commit prefixes pick the section, e.g. "fix(hooks): ..."
+fn classify(subject: &str) -> (&'static str, String) {
+    let (kind, text) = match subject.split_once(": ") {
+        Some((prefix, text)) if !prefix.contains(' ') => {
+            (prefix.split('(').next().unwrap_or(prefix), text)
+        }
+        _ => ("", subject),
+    };
+    let section = match kind.trim_end_matches('!') {
+        "feat" => "Added",
+        "fix" => "Fixed",
+        _ => "Changed",
+    };
+    (section, text.to_string())
+}
+
+/// Keep a Changelog's section order; other headings follow in the order found.
+const SECTIONS: &[&str] = &[
+    "Added",
+    "Changed",
+    "Deprecated",
+    "Removed",
+    "Fixed",
+    "Security",
+];
+
+// The release heading, any notes, then each `### Section` with its items
+fn release_section(
+    version: &Version,
+    date: &str,
+    notes: &[&str],
+    groups: &[(String, Vec<String>)],
+) -> String {
+    let mut section = format!("## [{version}] - {date}\n");
+    if !notes.is_empty() {
+        section.push('\n');
+        for line in notes {
+            section.push_str(&format!("{line}\n"));
+        }
+    }
+    for (heading, items) in groups {
+        section.push_str(&format!("\n### {heading}\n\n"));
+        for item in items {
+            section.push_str(&format!("{item}\n"));
+        }
+    }
+    section
+}
+
+// Places the release above the previous one. Anything collected under
+// [Unreleased] moves into it, merged with `entries` by section heading, and
+// that heading is left empty
+fn insert_release(
+    changelog: &str,
+    version: &Version,
+    date: &str,
+    entries: &[(&str, String)],
+) -> String {
+    let is_heading = |l: &str| l.starts_with("## ");
+    let lines: Vec<&str> = changelog.lines().collect();
+    let unreleased = lines
+        .iter()
+        .position(|l| is_heading(l) && l.to_lowercase().contains("[unreleased]"));
+    let (start, end) = match unreleased {
+        Some(u) => {
+            let end = lines[u + 1..]
+                .iter()
+                .position(|l| is_heading(l))
+                .map_or(lines.len(), |i| u + 1 + i);
+            (u + 1, end)
+        }
+        None => (0, 0),
+    };
+
+    // Pending lines before any `### Section` are notes; the rest are grouped
+    let mut notes: Vec<&str> = Vec::new();
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+    let mut current = None;
+    for line in &lines[start..end] {
+        if let Some(heading) = line.strip_prefix("### ") {
+            let heading = heading.trim();
+            current = Some(
+                match groups
+                    .iter()
+                    .position(|(h, _)| h.eq_ignore_ascii_case(heading))
+                {
+                    Some(i) => i,
+                    None => {
+                        groups.push((heading.to_string(), Vec::new()));
+                        groups.len() - 1
+                    }
+                },
+            );
+        } else if let Some(i) = current {
+            if !line.trim().is_empty() {
+                groups[i].1.push(line.to_string());
+            }
+        } else {
+            notes.push(line);
+        }
+    }
+    for (heading, text) in entries {
+        let item = format!("- {text}");
+        match groups
+            .iter_mut()
+            .find(|(h, _)| h.eq_ignore_ascii_case(heading))
+        {
+            Some((_, items)) => items.push(item),
+            None => groups.push((heading.to_string(), vec![item])),
+        }
+    }
+    groups.retain(|(_, items)| !items.is_empty());
+    let rank = |h: &str| {
+        SECTIONS
+            .iter()
+            .position(|s| s.eq_ignore_ascii_case(h))
+            .unwrap_or(SECTIONS.len())
+    };
+    groups.sort_by_key(|(h, _)| rank(h));
+    while notes.first().is_some_and(|l| l.trim().is_empty()) {
+        notes.remove(0);
+    }
+    while notes.last().is_some_and(|l| l.trim().is_empty()) {
+        notes.pop();
+    }
+    let section = release_section(version, date, &notes, &groups);
+
+    let mut out: Vec<String> = Vec::new();
+    match unreleased {
+        Some(u) => {
+            out.extend(lines[..=u].iter().map(|l| l.to_string()));
+            out.push(String::new());
+            out.push(section.trim_end().to_string());
+            out.push(String::new());
+            out.extend(lines[end..].iter().map(|l| l.to_string()));
+        }
+        None => {
+            let first = lines
+                .iter()
+                .position(|l| is_heading(l))
+                .unwrap_or(lines.len());
+            out.extend(lines[..first].iter().map(|l| l.to_string()));
+            if out.last().is_some_and(|l| !l.is_empty()) {
+                out.push(String::new());
+            }
+            out.push(section.trim_end().to_string());
+            out.push(String::new());
+            out.extend(lines[first..].iter().map(|l| l.to_string()));
+        }
+    }
+    while out.last().is_some_and(|l| l.is_empty()) {
+        out.pop();
+    }
+    out.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(changelog: &str, entries: &[(&str, &str)]) -> String {
+        let entries: Vec<_> = entries.iter().map(|(s, t)| (*s, t.to_string())).collect();
+        insert_release(changelog, &Version::new(1, 1, 0), "2026-01-02", &entries)
+    }
+
+    #[test]
+    fn classifies_conventional_commits() {
+        assert_eq!(classify("feat: add x"), ("Added", "add x".into()));
+        assert_eq!(classify("fix(hooks): quote y"), ("Fixed", "quote y".into()));
+        assert_eq!(classify("feat!: drop z"), ("Added", "drop z".into()));
+        assert_eq!(classify("chore: tidy"), ("Changed", "tidy".into()));
+        assert_eq!(
+            classify("Update docs: typo"),
+            ("Changed", "Update docs: typo".into())
+        );
+    }
+
+    #[test]
+    fn merges_pending_entries_by_heading() {
+        let changelog = "# Changelog\n\n## [Unreleased]\n\nSome notes.\n\n### Fixed\n\n- pending fix\n\n### added\n- pending feature\n  continued\n\n## [1.0.0] - 2025-01-01\n\n- first\n";
+        assert_eq!(
+            release(changelog, &[("Added", "git feature"), ("Changed", "git change")]),
+            "# Changelog\n\n## [Unreleased]\n\n## [1.1.0] - 2026-01-02\n\nSome notes.\n\n### added\n\n- pending feature\n  continued\n- git feature\n\n### Changed\n\n- git change\n\n### Fixed\n\n- pending fix\n\n## [1.0.0] - 2025-01-01\n\n- first\n"
+        );
+    }
+
+    #[test]
+    fn inserts_above_the_previous_release_without_unreleased() {
+        let changelog = "# Changelog\n\n## [1.0.0] - 2025-01-01\n";
+        assert_eq!(
+            release(changelog, &[("Fixed", "bug")]),
+            "# Changelog\n\n## [1.1.0] - 2026-01-02\n\n### Fixed\n\n- bug\n\n## [1.0.0] - 2025-01-01\n"
+        );
+    }
+
+    #[test]
+    fn new_changelog_gets_an_empty_release() {
+        let out = release(&keep_a_changelog_header(), &[]);
+        assert!(out.ends_with("## [Unreleased]\n\n## [1.1.0] - 2026-01-02\n"));
+    }
+}
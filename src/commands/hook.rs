@@ -0,0 +1,515 @@
+use crate::hooks::{HookEvent, HooksConfig, PLUGIN_ROOT_VAR};
+use crate::utils;
+use anyhow::{anyhow, Context, Result};
+use clap::Subcommand;
+use colored::*;
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::hash_map::RandomState;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Claude Code's default when a hook sets no timeout.
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+#[derive(Subcommand)]
+pub enum Action {
+    /// Run the hooks registered for an event against a sample payload
+    Test {
+        /// Event to simulate (e.g. PreToolUse, SessionStart)
+        #[arg(value_enum)]
+        event: HookEvent,
+        /// Tool name for PreToolUse/PostToolUse, checked against matchers
+        /// (defaults to the first tool the configured matchers name, else Bash)
+        #[arg(long)]
+        tool: Option<String>,
+        /// Tool input as a JSON object (defaults to a sample for the tool)
+        #[arg(long)]
+        input: Option<String>,
+        /// Prompt text for UserPromptSubmit
+        #[arg(long)]
+        prompt: Option<String>,
+        /// Send this JSON file as the payload instead of a generated one
+        #[arg(long, conflicts_with_all = ["input", "prompt"])]
+        payload: Option<PathBuf>,
+        /// Seconds to wait for hooks that do not set their own timeout
+        #[arg(long, default_value_t = DEFAULT_TIMEOUT_SECS)]
+        timeout: u64,
+        /// Print the payload sent on stdin
+        #[arg(short, long)]
+        verbose: bool,
+    },
+}
+
+pub fn run(plugin_flag: Option<String>, action: Action) -> Result<()> {
+    let root = utils::resolve_plugin_root(&plugin_flag)?;
+    let root = root.canonicalize().unwrap_or(root);
+    println!(
+        "🔧 Targeting plugin: {}",
+        root.file_name().unwrap().to_string_lossy().bold()
+    );
+
+    match action {
+        Action::Test {
+            event,
+            tool,
+            input,
+            prompt,
+            payload,
+            timeout,
+            verbose,
+        } => {
+            let config = HooksConfig::load_or_default(&root)?;
+            let tool = match tool {
+                Some(tool) => tool,
+                None => default_tool(&config, event)?,
+            };
+            let hooks = matching_hooks(&config, &root, event, &tool, timeout)?;
+            if hooks.is_empty() {
+                let scope = match uses_tool_matcher(event) {
+                    true => format!(" match tool \"{tool}\""),
+                    false => " are configured".to_string(),
+                };
+                return Err(anyhow!(
+                    "No {} hooks{scope}. Create one with `cforge add hook --event {}`",
+                    event.name(),
+                    event.name()
+                ));
+            }
+
+            let cwd = std::env::current_dir()?;
+            // The sample transcript is deleted when `_transcript` drops, after
+            // every hook has run
+            let (payload, _transcript) = match payload {
+                Some(path) => {
+                    let content = fs::read_to_string(&path)
+                        .with_context(|| format!("Failed to read {path:?}"))?;
+                    let payload = serde_json::from_str(&content)
+                        .with_context(|| format!("Invalid JSON payload {path:?}"))?;
+                    (payload, None)
+                }
+                None => {
+                    let input = match input {
+                        Some(raw) => serde_json::from_str(&raw).context("Invalid --input JSON")?,
+                        None => sample_tool_input(&tool, &cwd),
+                    };
+                    let (payload, transcript) = sample_payload(event, &tool, input, prompt, &cwd)?;
+                    (payload, Some(transcript))
+                }
+            };
+            let stdin = serde_json::to_string(&payload)?;
+            if verbose {
+                println!("{}", "Payload:".bold());
+                println!("{}", serde_json::to_string_pretty(&payload)?.dimmed());
+            }
+
+            let mut failures = 0;
+            for hook in &hooks {
+                println!();
+                match &hook.matcher {
+                    Some(m) => println!("🪝 {} [{m}] → {}", event.name(), hook.command.cyan()),
+                    None => println!("🪝 {} → {}", event.name(), hook.command.cyan()),
+                }
+                if !hook.registered {
+                    println!(
+                        "   {}",
+                        "not registered in hooks/hooks.json; running the script directly".yellow()
+                    );
+                }
+                let outcome = execute(&hook.command, &root, &cwd, &stdin, hook.timeout)?;
+                if !report(event, &outcome) {
+                    failures += 1;
+                }
+            }
+
+            if failures > 0 {
+                return Err(anyhow!("{failures} of {} hook(s) failed", hooks.len()));
+            }
+        }
+    }
+    Ok(())
+}
+
+// A hook command selected for the simulated event
+struct SelectedHook {
+    matcher: Option<String>,
+    command: String,
+    timeout: Duration,
+    registered: bool,
+}
+
+// Hooks from hooks.json whose matcher accepts `tool`. Without any wiring,
+// falls back to the script `add hook` would have created for the event
+fn matching_hooks(
+    config: &HooksConfig,
+    root: &Path,
+    event: HookEvent,
+    tool: &str,
+    default_timeout: u64,
+) -> Result<Vec<SelectedHook>> {
+    let mut selected = Vec::new();
+    for group in config.groups(event.name())? {
+        if uses_tool_matcher(event) && !matcher_accepts(group.matcher.as_deref(), tool)? {
+            continue;
+        }
+        for hook in group.hooks {
+            let Some(command) = hook.command.filter(|_| hook.kind == "command") else {
+                continue;
+            };
+            selected.push(SelectedHook {
+                matcher: group.matcher.clone(),
+                command,
+                timeout: Duration::from_secs(hook.timeout.unwrap_or(default_timeout)),
+                registered: true,
+            });
+        }
+    }
+
+    if selected.is_empty() && config.hooks.get(event.name()).is_none() {
+        let script = root.join("hooks").join(event.script_name());
+        if script.is_file() {
+            selected.push(SelectedHook {
+                matcher: None,
+                command: format!("{PLUGIN_ROOT_VAR}/hooks/{}", event.script_name()),
+                timeout: Duration::from_secs(default_timeout),
+                registered: false,
+            });
+        }
+    }
+    Ok(selected)
+}
+
+// The first plain tool name in the event's matchers, so that a bare
+// `hook test PreToolUse` exercises something
+fn default_tool(config: &HooksConfig, event: HookEvent) -> Result<String> {
+    let named = config
+        .groups(event.name())?
+        .into_iter()
+        .filter_map(|g| g.matcher)
+        .flat_map(|m| m.split('|').map(str::to_string).collect::<Vec<_>>())
+        .find(|t| !t.is_empty() && t.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'));
+    Ok(named.unwrap_or_else(|| "Bash".to_string()))
+}
+
+fn uses_tool_matcher(event: HookEvent) -> bool {
+    matches!(event, HookEvent::PreToolUse | HookEvent::PostToolUse)
+}
+
+// Matchers are regexes against the tool name; empty or "*" matches everything
+fn matcher_accepts(matcher: Option<&str>, tool: &str) -> Result<bool> {
+    match matcher {
+        None | Some("") | Some("*") => Ok(true),
+        Some(m) => {
+            let re = Regex::new(&format!("^(?:{m})$"))
+                .with_context(|| format!("Invalid matcher regex \"{m}\""))?;
+            Ok(re.is_match(tool))
+        }
+    }
+}
+
+fn sample_tool_input(tool: &str, cwd: &Path) -> Value {
+    let file = cwd.join("src/main.rs").to_string_lossy().into_owned();
+    match tool {
+        "Bash" => json!({ "command": "ls -la", "description": "List files" }),
+        "Write" => json!({ "file_path": file, "content": "fn main() {}\n" }),
+        "Edit" | "MultiEdit" => json!({
+            "file_path": file,
+            "old_string": "fn main() {}",
+            "new_string": "fn main() {\n    println!(\"hi\");\n}",
+        }),
+        "Read" => json!({ "file_path": file }),
+        "Glob" => json!({ "pattern": "**/*.rs" }),
+        "Grep" => json!({ "pattern": "TODO", "path": cwd }),
+        "WebFetch" => json!({ "url": "https://example.com", "prompt": "Summarize the page" }),
+        _ => json!({}),
+    }
+}
+
+// Builds the stdin document Claude Code sends for `event`
+fn sample_payload(
+    event: HookEvent,
+    tool: &str,
+    tool_input: Value,
+    prompt: Option<String>,
+    cwd: &Path,
+) -> Result<(Value, Transcript)> {
+    let session_id = session_id();
+    let transcript = write_transcript(&session_id, cwd)?;
+    let mut payload = json!({
+        "session_id": session_id,
+        "transcript_path": transcript.0,
+        "cwd": cwd,
+        "hook_event_name": event.name(),
+    });
+
+    let fields = match event {
+        HookEvent::PreToolUse => json!({ "tool_name": tool, "tool_input": tool_input }),
+        HookEvent::PostToolUse => json!({
+            "tool_name": tool,
+            "tool_input": tool_input,
+            "tool_response": { "success": true },
+        }),
+        HookEvent::UserPromptSubmit => json!({
+            "prompt": prompt.unwrap_or_else(|| "Refactor the parser".to_string()),
+        }),
+        HookEvent::SessionStart => json!({ "source": "startup" }),
+        HookEvent::SessionEnd => json!({ "reason": "exit" }),
+        HookEvent::Stop | HookEvent::SubagentStop => json!({ "stop_hook_active": false }),
+        HookEvent::Notification => json!({ "message": "Claude needs your permission to use Bash" }),
+        HookEvent::PreCompact => json!({ "trigger": "manual", "custom_instructions": "" }),
+    };
+    for (key, value) in fields.as_object().unwrap() {
+        payload[key] = value.clone();
+    }
+    Ok((payload, transcript))
+}
+
+// Random enough for a throwaway session, formatted like a UUID
+fn session_id() -> String {
+    let random = || RandomState::new().build_hasher().finish();
+    let hex = format!("{:016x}{:016x}", random(), random());
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+// A sample transcript file, removed when dropped
+struct Transcript(PathBuf);
+
+impl Drop for Transcript {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+// Hooks that inspect the transcript get a small but well-formed JSONL file
+fn write_transcript(session_id: &str, cwd: &Path) -> Result<Transcript> {
+    let path = std::env::temp_dir().join(format!("cforge-hook-test-{session_id}.jsonl"));
+    let entry = json!({
+        "type": "user",
+        "sessionId": session_id,
+        "cwd": cwd,
+        "message": { "role": "user", "content": "cforge hook test" },
+    });
+    fs::write(&path, format!("{entry}\n")).with_context(|| format!("Failed to write {path:?}"))?;
+    Ok(Transcript(path))
+}
+
+struct Outcome {
+    status: Option<i32>,
+    timed_out: bool,
+    elapsed: Duration,
+    stdout: String,
+    stderr: String,
+}
+
+// Runs the command through the shell the way Claude Code does, killing it
+// once `timeout` elapses
+fn execute(
+    command: &str,
+    root: &Path,
+    cwd: &Path,
+    stdin: &str,
+    timeout: Duration,
+) -> Result<Outcome> {
+    let start = Instant::now();
+    let mut command_line = Command::new("sh");
+    own_process_group(&mut command_line);
+    let mut child = command_line
+        .arg("-c")
+        .arg(command)
+        .current_dir(cwd)
+        .env("CLAUDE_PLUGIN_ROOT", root)
+        .env("CLAUDE_PROJECT_DIR", cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run `{command}`"))?;
+
+    // Written from its own thread, so a hook that never reads a payload larger
+    // than the pipe buffer still times out. One that closes the pipe early is
+    // not an error
+    let mut pipe = child.stdin.take().unwrap();
+    let payload = stdin.to_string();
+    thread::spawn(move || pipe.write_all(payload.as_bytes()));
+    let stdout = drain(child.stdout.take().unwrap());
+    let stderr = drain(child.stderr.take().unwrap());
+
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if start.elapsed() >= timeout {
+            timed_out = true;
+            kill_tree(&mut child)?;
+            break child.wait()?;
+        }
+        thread::sleep(Duration::from_millis(20));
+    };
+
+    Ok(Outcome {
+        status: status.code(),
+        timed_out,
+        elapsed: start.elapsed(),
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+// The script runs under `sh -c`, so killing only the shell would leave the
+// script holding stdout open; give it a process group we can kill as a whole
+#[cfg(unix)]
+fn own_process_group(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    command.process_group(0);
+}
+
+#[cfg(not(unix))]
+fn own_process_group(_command: &mut Command) {}
+
+#[cfg(unix)]
+fn kill_tree(child: &mut Child) -> Result<()> {
+    let group = format!("-{}", child.id());
+    let killed = Command::new("kill")
+        .args(["-KILL", "--", &group])
+        .status()
+        .is_ok_and(|s| s.success());
+    if !killed {
+        child.kill()?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn kill_tree(child: &mut Child) -> Result<()> {
+    Ok(child.kill()?)
+}
+
+fn drain(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        String::from_utf8_lossy(&buf).into_owned()
+    })
+}
+
+// Prints how Claude Code would treat the result; returns false if the hook errored
+fn report(event: HookEvent, outcome: &Outcome) -> bool {
+    let elapsed = format!("{}ms", outcome.elapsed.as_millis());
+    let ok = match (outcome.timed_out, outcome.status) {
+        (true, _) => {
+            println!("   {} after {elapsed}", "timed out".red().bold());
+            false
+        }
+        (false, Some(0)) => {
+            println!("   exit code: {} (success) in {elapsed}", "0".green());
+            true
+        }
+        (false, Some(2)) => {
+            println!(
+                "   exit code: {} (blocking; stderr is fed back to Claude) in {elapsed}",
+                "2".yellow()
+            );
+            true
+        }
+        (false, Some(code)) => {
+            println!(
+                "   exit code: {} (non-blocking error; shown to the user) in {elapsed}",
+                code.to_string().red()
+            );
+            false
+        }
+        (false, None) => {
+            println!("   {} in {elapsed}", "killed by a signal".red());
+            false
+        }
+    };
+
+    print_stream("stdout", &outcome.stdout);
+    print_stream("stderr", &outcome.stderr);
+
+    if outcome.status == Some(0) {
+        match serde_json::from_str::<Value>(outcome.stdout.trim()) {
+            Ok(Value::Object(output)) => print_decision(&output),
+            _ if !outcome.stdout.trim().is_empty() && adds_stdout_context(event) => {
+                println!("   {} stdout is added to Claude's context", "→".bold());
+            }
+            _ => {}
+        }
+    }
+    ok
+}
+
+fn print_stream(label: &str, text: &str) {
+    let text = text.trim_end();
+    if text.is_empty() {
+        return;
+    }
+    println!("   {}", format!("{label}:").bold());
+    for line in text.lines() {
+        println!("     {}", line.dimmed());
+    }
+}
+
+fn adds_stdout_context(event: HookEvent) -> bool {
+    matches!(event, HookEvent::UserPromptSubmit | HookEvent::SessionStart)
+}
+
+// Summarises the JSON control fields a hook can print on stdout
+fn print_decision(output: &serde_json::Map<String, Value>) {
+    let text = |v: Option<&Value>| v.and_then(Value::as_str).map(str::to_string);
+    let specific = output.get("hookSpecificOutput");
+
+    let decision = text(specific.and_then(|s| s.get("permissionDecision")))
+        .or_else(|| text(output.get("decision")));
+    let reason = text(specific.and_then(|s| s.get("permissionDecisionReason")))
+        .or_else(|| text(output.get("reason")));
+    if let Some(decision) = decision {
+        let shown = match decision.as_str() {
+            "allow" | "approve" => decision.green(),
+            "deny" | "block" => decision.red(),
+            _ => decision.yellow(),
+        };
+        match reason {
+            Some(reason) => println!("   {} {shown}: {reason}", "decision:".bold()),
+            None => println!("   {} {shown}", "decision:".bold()),
+        }
+    }
+    if let Some(context) = text(specific.and_then(|s| s.get("additionalContext"))) {
+        println!("   {} {context}", "additionalContext:".bold());
+    }
+    if output.get("continue") == Some(&Value::Bool(false)) {
+        let reason = text(output.get("stopReason")).unwrap_or_default();
+        println!("   {} {reason}", "stops Claude:".red().bold());
+    }
+    if let Some(message) = text(output.get("systemMessage")) {
+        println!("   {} {message}", "systemMessage:".bold());
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timeout_holds_when_the_hook_ignores_a_large_payload() {
+        let dir = tempfile::tempdir().unwrap();
+        let payload = "x".repeat(4 * 1024 * 1024);
+        let timeout = Duration::from_millis(300);
+        let outcome = execute("sleep 10", dir.path(), dir.path(), &payload, timeout).unwrap();
+        assert!(outcome.timed_out);
+        assert!(outcome.elapsed < Duration::from_secs(5));
+    }
+}
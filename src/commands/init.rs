@@ -1,41 +1,35 @@
-use crate::templates;
+use crate::commands::register;
+use crate::kits;
+use crate::templates::Templates;
 use crate::utils;
 use anyhow::{anyhow, Result};
-use dialoguer::Input;
+use serde_json::json;
 use std::path::Path;
 
 pub fn init_marketplace(name_opt: Option<String>, desc_opt: Option<String>) -> Result<()> {
     // 1. Get Name & Description
-    let name = match name_opt {
-        Some(n) => n,
-        None => Input::new()
-            .with_prompt("Marketplace Name")
-            .interact_text()?,
-    };
-    let desc = get_description(desc_opt)?;
+    let name = utils::prompt(name_opt, "Marketplace Name", "--name")?;
+    let desc = utils::get_description(desc_opt)?;
 
     let root = Path::new(&name);
     if root.exists() {
         return Err(anyhow!("Directory '{name}' already exists"));
     }
 
+    // Render first, so a broken template leaves nothing half-created
+    let templates = Templates::discover(&std::env::current_dir()?);
+    let manifest = templates.marketplace_manifest(&name, &desc)?;
+    let ctx = templates.claude_context()?;
+    let readme = templates.marketplace_readme(&name, &desc)?;
+
     // 2. Scaffold Structure
     utils::create_dir(root)?;
     utils::create_dir(&root.join("plugins"))?;
     utils::create_dir(&root.join(".claude-plugin"))?;
 
     // 3. Write Files
-    let manifest = templates::get_marketplace_manifest(&name, &desc);
     utils::create_file(&root.join(".claude-plugin/marketplace.json"), &manifest)?;
-
-    let ctx = templates::get_claude_context();
-    utils::create_file(&root.join("CLAUDE.md"), ctx)?;
-
-    let readme = format!(
-        "# {name}
-
-{desc}"
-    );
+    utils::create_file(&root.join("CLAUDE.md"), &ctx)?;
     utils::create_file(&root.join("README.md"), &readme)?;
 
     println!("\n✅ Marketplace initialized! To start:");
@@ -44,15 +38,16 @@ pub fn init_marketplace(name_opt: Option<String>, desc_opt: Option<String>) -> R
     Ok(())
 }
 
-pub fn init_plugin(name_opt: Option<String>, desc_opt: Option<String>) -> Result<()> {
+pub fn init_plugin(
+    name_opt: Option<String>,
+    desc_opt: Option<String>,
+    kit: Option<String>,
+    register: bool,
+) -> Result<()> {
     // 1. Get Name & Description
-    let name = match name_opt {
-        Some(n) => n,
-        None => Input::new()
-            .with_prompt("Plugin Name (kebab-case)")
-            .interact_text()?,
-    };
-    let desc = get_description(desc_opt)?;
+    let name = utils::prompt(name_opt, "Plugin Name (kebab-case)", "--name")?;
+    let name = utils::slug(&name)?;
+    let desc = utils::get_description(desc_opt)?;
 
     // Check context: Are we in a marketplace?
     // (Simple check: prefer creating in ./plugins/ if it exists)
@@ -67,27 +62,51 @@ pub fn init_plugin(name_opt: Option<String>, desc_opt: Option<String>) -> Result
         return Err(anyhow!("Plugin directory already exists at {target_dir:?}"));
     }
 
-    // 2. Structure
+    // Render first, so a broken template leaves nothing half-created
+    let templates = Templates::discover(&cwd).with("plugin", &name);
+    let files = match &kit {
+        Some(spec) => {
+            let vars = json!({ "name": name, "slug": name, "description": desc });
+            kits::load(spec, &templates, &vars)?
+        }
+        None => Vec::new(),
+    };
+    let has = |path: &str| files.iter().any(|f| f.path == Path::new(path));
+    let manifest = match has(".claude-plugin/plugin.json") {
+        true => None,
+        false => Some(templates.plugin_manifest(&name, &desc)?),
+    };
+    let readme = match has("README.md") {
+        true => None,
+        false => Some(templates.plugin_readme(&name, &desc)?),
+    };
+
+    // 2. Structure (a kit brings its own)
     utils::create_dir(&target_dir)?;
-    utils::create_dir(&target_dir.join("commands"))?;
-    utils::create_dir(&target_dir.join("skills"))?;
-    utils::create_dir(&target_dir.join("agents"))?;
-    utils::create_dir(&target_dir.join("hooks"))?;
+    if kit.is_none() {
+        utils::create_dir(&target_dir.join("commands"))?;
+        utils::create_dir(&target_dir.join("skills"))?;
+        utils::create_dir(&target_dir.join("agents"))?;
+        utils::create_dir(&target_dir.join("hooks"))?;
+    }
     utils::create_dir(&target_dir.join(".claude-plugin"))?;
 
-    // 3. Manifest
-    let manifest = templates::get_plugin_manifest(&name, &desc);
-    utils::create_file(&target_dir.join(".claude-plugin/plugin.json"), &manifest)?;
+    // 3. Manifest, README and starter kit files
+    if let Some(manifest) = manifest {
+        utils::create_file(&target_dir.join(".claude-plugin/plugin.json"), &manifest)?;
+    }
+    if let Some(readme) = readme {
+        utils::create_file(&target_dir.join("README.md"), &readme)?;
+    }
+    for file in &files {
+        file.write(&target_dir)?;
+    }
 
-    utils::create_file(&target_dir.join("README.md"), &format!("# {name}"))?;
+    // 4. Register with the enclosing marketplace, if any
+    if let Some(market_root) = utils::find_marketplace_root(&cwd).filter(|_| register) {
+        register::register_new(&market_root, &target_dir)?;
+    }
 
     println!("\n✅ Plugin created at {target_dir:?}");
     Ok(())
 }
-
-fn get_description(arg: Option<String>) -> Result<String> {
-    match arg {
-        Some(d) => Ok(d),
-        None => Ok(Input::new().with_prompt("Description").interact_text()?),
-    }
-}
@@ -1,63 +1,64 @@
+use crate::frontmatter;
+use crate::hooks;
+use crate::manifest::{Marketplace, PluginEntry, PluginManifest};
+use crate::utils::sorted_entries;
 use anyhow::Result;
+use clap::ValueEnum;
 use colored::*;
-use serde::Deserialize;
+use serde::Serialize;
+use serde_json::{Map, Value};
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
-// Marketplace schema (same as validate.rs)
-#[derive(Deserialize)]
-struct Marketplace {
-    #[allow(dead_code)]
-    name: String,
-    #[allow(dead_code)]
-    description: Option<String>,
-    #[allow(dead_code)]
-    owner: Option<Owner>,
-    plugins: Vec<PluginEntry>,
+/// Output format for listings.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Table,
+    Json,
+    Yaml,
+    Csv,
 }
 
-#[derive(Deserialize)]
-struct Owner {
-    #[allow(dead_code)]
+#[derive(Serialize)]
+struct ComponentInfo {
     name: String,
-    #[allow(dead_code)]
-    email: Option<String>,
+    #[serde(rename = "type")]
+    component_type: String,
+    #[serde(rename = "path")]
+    file_path: String,
+    #[serde(skip)]
+    description: Option<String>,
+    frontmatter: Map<String, Value>,
 }
 
-#[derive(Deserialize)]
-struct PluginEntry {
+// Everything `list` knows about a marketplace entry
+#[derive(Serialize)]
+struct PluginRecord {
     name: String,
-    version: String,
-    source: String,
+    version: Option<String>,
+    source: &'static str,
+    path: Option<String>,
+    status: &'static str,
     description: Option<String>,
+    components: ComponentCounts,
 }
 
-// Plugin schema (currently unused but kept for future enhancements)
-#[allow(dead_code)]
-#[derive(Deserialize)]
-struct Plugin {
-    name: String,
-    version: String,
-    skills: Option<Vec<String>>,
-    commands: Option<Vec<String>>,
-    agents: Option<Vec<String>>,
-    hooks: Option<Vec<String>>,
-}
-
-struct ComponentInfo {
-    name: String,
-    component_type: String,
-    file_path: String,
+#[derive(Default, Serialize)]
+struct ComponentCounts {
+    skills: usize,
+    commands: usize,
+    agents: usize,
+    hooks: usize,
 }
 
 // Main entry point with auto-detection
-pub fn list(path_opt: Option<String>, verbose: bool) -> Result<()> {
+pub fn list(path_opt: Option<String>, verbose: bool, format: Format) -> Result<()> {
     let root = resolve_path(path_opt)?;
 
     if is_marketplace(&root)? {
-        list_plugins(&root, verbose)
+        list_plugins(&root, verbose, format)
     } else if is_plugin(&root)? {
-        list_components(&root, verbose)
+        list_components(&root, verbose, format)
     } else {
         Err(anyhow::anyhow!("Not in a marketplace or plugin directory"))
     }
@@ -83,11 +84,47 @@ fn is_plugin(path: &Path) -> Result<bool> {
 }
 
 // List plugins in marketplace
-fn list_plugins(root: &Path, verbose: bool) -> Result<()> {
-    let marketplace_path = root.join(".claude-plugin/marketplace.json");
-    let content = fs::read_to_string(&marketplace_path)?;
-    let marketplace: Marketplace = serde_json::from_str(&content)
-        .map_err(|e| anyhow::anyhow!("Invalid marketplace.json: {}", e))?;
+fn list_plugins(root: &Path, verbose: bool, format: Format) -> Result<()> {
+    let marketplace = Marketplace::load(&root.join(".claude-plugin/marketplace.json"))?;
+
+    if format != Format::Table {
+        let records: Vec<_> = marketplace
+            .plugins
+            .iter()
+            .map(|plugin| plugin_record(plugin, root))
+            .collect();
+        return match format {
+            Format::Csv => print_csv(
+                &[
+                    "name",
+                    "version",
+                    "source",
+                    "path",
+                    "status",
+                    "description",
+                    "skills",
+                    "commands",
+                    "agents",
+                    "hooks",
+                ],
+                records.iter().map(|r| {
+                    vec![
+                        r.name.clone(),
+                        r.version.clone().unwrap_or_default(),
+                        r.source.to_string(),
+                        r.path.clone().unwrap_or_default(),
+                        r.status.to_string(),
+                        r.description.clone().unwrap_or_default(),
+                        r.components.skills.to_string(),
+                        r.components.commands.to_string(),
+                        r.components.agents.to_string(),
+                        r.components.hooks.to_string(),
+                    ]
+                }),
+            ),
+            _ => print_records(&records, format),
+        };
+    }
 
     if marketplace.plugins.is_empty() {
         println!("No plugins found in marketplace");
@@ -104,79 +141,189 @@ fn list_plugins(root: &Path, verbose: bool) -> Result<()> {
 }
 
 // List components in plugin
-fn list_components(root: &Path, verbose: bool) -> Result<()> {
-    let mut components = Vec::new();
+fn list_components(root: &Path, verbose: bool, format: Format) -> Result<()> {
+    let (components, legacy_skills) = find_components(root);
 
-    // Find skills
-    if let Ok(entries) = fs::read_dir(root.join("skills")) {
-        for entry in entries.flatten() {
-            if entry.path().extension().is_some_and(|ext| ext == "md") {
-                let name = entry.file_name().to_string_lossy().replace(".md", "");
-                components.push(ComponentInfo {
-                    name,
-                    component_type: "skill".to_string(),
-                    file_path: format!("skills/{}", entry.file_name().to_string_lossy()),
-                });
-            }
+    match format {
+        Format::Table => {}
+        Format::Csv => {
+            return print_csv(
+                &["name", "type", "path", "description", "frontmatter"],
+                components.iter().map(|c| {
+                    vec![
+                        c.name.clone(),
+                        c.component_type.clone(),
+                        c.file_path.clone(),
+                        c.description.clone().unwrap_or_default(),
+                        Value::Object(c.frontmatter.clone()).to_string(),
+                    ]
+                }),
+            )
         }
+        _ => return print_records(&components, format),
     }
 
-    // Find commands
-    if let Ok(entries) = fs::read_dir(root.join("commands")) {
-        for entry in entries.flatten() {
-            if entry.path().extension().is_some_and(|ext| ext == "md") {
-                let name = entry.file_name().to_string_lossy().replace(".md", "");
-                components.push(ComponentInfo {
-                    name,
-                    component_type: "command".to_string(),
-                    file_path: format!("commands/{}", entry.file_name().to_string_lossy()),
-                });
-            }
+    if components.is_empty() {
+        println!("No components found in plugin");
+        return Ok(());
+    }
+
+    print_components_table(&components, verbose);
+
+    if legacy_skills > 0 {
+        println!(
+            "\n{} {legacy_skills} skill(s) use the legacy flat layout; move them to skills/<name>/SKILL.md",
+            "⚠️ ".yellow()
+        );
+    }
+
+    Ok(())
+}
+
+// Skills, commands, agents and hook scripts in a plugin, with the number of
+// skills in the legacy flat layout
+fn find_components(root: &Path) -> (Vec<ComponentInfo>, usize) {
+    let mut components = Vec::new();
+    let entries = |dir: &str| sorted_entries(&root.join(dir)).unwrap_or_default();
+    let file_name = |path: &Path| {
+        path.file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+    };
+
+    // Find skills (skills/<name>/SKILL.md, or the legacy skills/<name>.md)
+    let mut legacy_skills = 0;
+    for path in entries("skills") {
+        let name = file_name(&path);
+        if path.join("SKILL.md").is_file() {
+            let file = format!("skills/{name}/SKILL.md");
+            components.push(component(root, name, "skill", file));
+        } else if path.extension().is_some_and(|ext| ext == "md") {
+            legacy_skills += 1;
+            let file = format!("skills/{name}");
+            components.push(component(root, name.replace(".md", ""), "skill", file));
         }
     }
 
-    // Find agents
-    if let Ok(entries) = fs::read_dir(root.join("agents")) {
-        for entry in entries.flatten() {
-            if entry.path().extension().is_some_and(|ext| ext == "md") {
-                let name = entry.file_name().to_string_lossy().replace(".md", "");
-                components.push(ComponentInfo {
-                    name,
-                    component_type: "agent".to_string(),
-                    file_path: format!("agents/{}", entry.file_name().to_string_lossy()),
-                });
+    // Find commands and agents
+    for (dir, component_type) in [("commands", "command"), ("agents", "agent")] {
+        for path in entries(dir) {
+            if path.extension().is_some_and(|ext| ext == "md") {
+                let name = file_name(&path);
+                let file = format!("{dir}/{name}");
+                components.push(component(
+                    root,
+                    name.replace(".md", ""),
+                    component_type,
+                    file,
+                ));
             }
         }
     }
 
-    // Find hooks
-    if let Ok(entries) = fs::read_dir(root.join("hooks")) {
-        for entry in entries.flatten() {
-            if entry.path().extension().is_some_and(|ext| ext == "sh") {
-                let name = entry.file_name().to_string_lossy().replace(".sh", "");
-                components.push(ComponentInfo {
-                    name,
-                    component_type: "hook".to_string(),
-                    file_path: format!("hooks/{}", entry.file_name().to_string_lossy()),
-                });
-            }
+    // Find hooks: scripts hooks.json runs, whatever their extension
+    for path in hooks::scripts(root) {
+        let name = path.file_stem().unwrap_or_default().to_string_lossy();
+        let file = format!("hooks/{}", file_name(&path));
+        components.push(component(root, name.into_owned(), "hook", file));
+    }
+
+    (components, legacy_skills)
+}
+
+// A component and its frontmatter, which is empty for files without any
+fn component(root: &Path, name: String, component_type: &str, file_path: String) -> ComponentInfo {
+    let frontmatter = fs::read_to_string(root.join(&file_path))
+        .ok()
+        .and_then(|content| frontmatter::parse(&content).ok())
+        .unwrap_or_default();
+    ComponentInfo {
+        name,
+        component_type: component_type.to_string(),
+        file_path,
+        description: frontmatter
+            .get("description")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        frontmatter,
+    }
+}
+
+// Full record for a marketplace entry, looking into local plugins
+fn plugin_record(plugin: &PluginEntry, root: &Path) -> PluginRecord {
+    let path = plugin.source.local_path(root);
+    let mut components = ComponentCounts::default();
+    for component in path.iter().flat_map(|p| find_components(p).0) {
+        match component.component_type.as_str() {
+            "skill" => components.skills += 1,
+            "command" => components.commands += 1,
+            "agent" => components.agents += 1,
+            _ => components.hooks += 1,
         }
     }
+    PluginRecord {
+        name: plugin.name.clone(),
+        version: plugin.meta.version.clone(),
+        source: plugin.source.kind(),
+        path: path.map(|p| resolved(&p).display().to_string()),
+        status: status(plugin, root),
+        description: plugin.meta.description.clone(),
+        components,
+    }
+}
 
-    if components.is_empty() {
-        println!("No components found in plugin");
-        return Ok(());
+// Absolute form of `path`, even when it does not exist
+fn resolved(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| {
+        std::path::absolute(path)
+            .unwrap_or_else(|_| path.to_path_buf())
+            .components()
+            .filter(|c| *c != Component::CurDir)
+            .collect()
+    })
+}
+
+// valid, drift, invalid or missing for local plugins; remote or invalid otherwise
+fn status(plugin: &PluginEntry, root: &Path) -> &'static str {
+    match plugin.source.local_path(root) {
+        Some(path) => match PluginManifest::load(&path.join(".claude-plugin/plugin.json")) {
+            Ok(manifest) if plugin.drift(&manifest).is_empty() => "valid",
+            Ok(_) => "drift",
+            Err(_) if path.exists() => "invalid",
+            Err(_) => "missing",
+        },
+        None if plugin.source.check_remote().is_empty() => "remote",
+        None => "invalid",
     }
+}
 
-    print_components_table(&components, verbose);
+fn print_records<T: Serialize>(records: &[T], format: Format) -> Result<()> {
+    match format {
+        Format::Yaml => print!("{}", serde_yaml::to_string(records)?),
+        _ => println!("{}", serde_json::to_string_pretty(records)?),
+    }
+    Ok(())
+}
 
+// RFC 4180 CSV: fields with commas, quotes or line breaks are quoted
+fn print_csv(header: &[&str], rows: impl Iterator<Item = Vec<String>>) -> Result<()> {
+    let quote = |field: &str| match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    };
+    println!("{}", header.join(","));
+    for row in rows {
+        let fields: Vec<_> = row.iter().map(|f| quote(f)).collect();
+        println!("{}", fields.join(","));
+    }
     Ok(())
 }
 
 // Simple plugin list
 fn print_plugins_simple(plugins: &[PluginEntry]) {
     for plugin in plugins {
-        println!("{:<25} v{}", plugin.name, plugin.version);
+        println!("{:<25} v{}", plugin.name, display_version(plugin));
     }
 }
 
@@ -192,14 +339,15 @@ fn print_plugins_verbose(plugins: &[PluginEntry], root: &Path) -> Result<()> {
     println!("{}", "-".repeat(80));
 
     for plugin in plugins {
-        let plugin_path = root.join(&plugin.source);
-        let status = if plugin_path.join(".claude-plugin/plugin.json").exists() {
-            "valid".green()
-        } else {
-            "missing".red()
+        let status = match status(plugin, root) {
+            "valid" => "valid".green(),
+            "drift" => "drift".yellow(),
+            "remote" => "remote".cyan(),
+            other => other.red(),
         };
 
         let desc = plugin
+            .meta
             .description
             .as_deref()
             .unwrap_or("")
@@ -210,7 +358,7 @@ fn print_plugins_verbose(plugins: &[PluginEntry], root: &Path) -> Result<()> {
         println!(
             "{:<25} {:<10} {:<8} {}",
             plugin.name,
-            format!("v{}", plugin.version),
+            format!("v{}", display_version(plugin)),
             status,
             desc
         );
@@ -219,8 +367,38 @@ fn print_plugins_verbose(plugins: &[PluginEntry], root: &Path) -> Result<()> {
     Ok(())
 }
 
+fn display_version(plugin: &PluginEntry) -> &str {
+    plugin.meta.version.as_deref().unwrap_or("?")
+}
+
 // Component table
-fn print_components_table(components: &[ComponentInfo], _verbose: bool) {
+fn print_components_table(components: &[ComponentInfo], verbose: bool) {
+    if verbose {
+        println!(
+            "{:<20} {:<10} {:<30} {}",
+            "COMPONENT".bold(),
+            "TYPE".bold(),
+            "FILE".bold(),
+            "DESCRIPTION".bold()
+        );
+        println!("{}", "-".repeat(100));
+
+        for component in components {
+            let desc = component
+                .description
+                .as_deref()
+                .unwrap_or("")
+                .chars()
+                .take(40)
+                .collect::<String>();
+            println!(
+                "{:<20} {:<10} {:<30} {}",
+                component.name, component.component_type, component.file_path, desc
+            );
+        }
+        return;
+    }
+
     println!(
         "{:<20} {:<10} {}",
         "COMPONENT".bold(),
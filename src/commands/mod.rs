@@ -1,5 +1,11 @@
 pub mod add;
+pub mod bump;
+pub mod hook;
 pub mod init;
 pub mod list;
 pub mod register;
+pub mod remove;
+pub mod rename;
+pub mod sync;
+pub mod templates;
 pub mod validate;
@@ -1,16 +1,18 @@
+use crate::manifest::{Marketplace, PluginEntry, PluginManifest, PluginSource};
 use anyhow::{anyhow, Context, Result};
-use serde_json::Value;
-use std::fs;
-use std::path::Path;
+use colored::*;
+use std::path::{Component, Path};
 
-pub fn register_plugin(plugin_path_str: String) -> Result<()> {
+// What registering a single plugin did to the marketplace
+enum Outcome {
+    Registered,
+    Updated,
+    AlreadyRegistered,
+}
+
+pub fn register_plugin(plugin_path_str: String, update: bool) -> Result<()> {
     // 1. Locate Marketplace Manifest (must be in root)
-    let market_manifest = Path::new(".claude-plugin/marketplace.json");
-    if !market_manifest.exists() {
-        return Err(anyhow!(
-            "❌ No marketplace.json found. Run this from the marketplace root."
-        ));
-    }
+    let market_manifest = marketplace_manifest()?;
 
     // 2. Load Plugin Data
     let plugin_path = Path::new(&plugin_path_str);
@@ -21,36 +23,158 @@ pub fn register_plugin(plugin_path_str: String) -> Result<()> {
         ));
     }
 
-    let p_content = fs::read_to_string(&plugin_manifest)?;
-    let p_json: Value = serde_json::from_str(&p_content)?;
-    let p_name = p_json["name"]
-        .as_str()
-        .context("Plugin missing 'name' field")?;
-    let p_desc = p_json["description"].as_str().unwrap_or("");
-
     // 3. Update Marketplace Registry
-    let m_content = fs::read_to_string(market_manifest)?;
-    let mut m_json: Value = serde_json::from_str(&m_content)?;
-
-    if let Some(plugins) = m_json["plugins"].as_array_mut() {
-        // Check for duplicates
-        if plugins.iter().any(|p| p["name"] == p_name) {
-            println!("⚠️  Plugin '{p_name}' is already registered.");
+    let mut marketplace = Marketplace::load(market_manifest)?;
+    let (name, outcome) = upsert(&mut marketplace, &plugin_path_str, update)?;
+    match outcome {
+        Outcome::AlreadyRegistered => {
+            println!("⚠️  Plugin '{name}' is already registered (use --update to re-sync it).");
             return Ok(());
         }
-
-        // Add new entry
-        plugins.push(serde_json::json!({
-            "name": p_name,
-            "description": p_desc,
-            "source": plugin_path_str // Saves the relative path
-        }));
+        Outcome::Updated => println!("🔄 Updated '{name}' from its plugin.json."),
+        Outcome::Registered => println!("✅ Registered '{name}' in marketplace registry."),
     }
 
     // 4. Save
-    let file = fs::File::create(market_manifest)?;
-    serde_json::to_writer_pretty(file, &m_json)?;
+    marketplace.save(market_manifest)
+}
+
+/// Registers every plugin matched by `pattern` (default `plugins/*`) and
+/// reports marketplace entries whose local source no longer exists.
+pub fn register_all(pattern: Option<String>, update: bool) -> Result<()> {
+    let market_manifest = marketplace_manifest()?;
+    let mut marketplace = Marketplace::load(market_manifest)?;
+    let pattern = pattern.unwrap_or_else(|| "plugins/*".to_string());
 
-    println!("✅ Registered '{p_name}' in marketplace registry.");
+    let mut changed = 0;
+    let mut found = 0;
+    let matches = glob::glob(&pattern).with_context(|| format!("Invalid glob \"{pattern}\""))?;
+    for path in matches.filter_map(|p| p.ok()) {
+        if !path.join(".claude-plugin/plugin.json").is_file() {
+            continue;
+        }
+        found += 1;
+        let source = local_source(&path, Path::new("."));
+        match upsert(&mut marketplace, &source, update) {
+            Ok((name, Outcome::Registered)) => {
+                changed += 1;
+                println!("{} {name} ({source})", "Registered:".green());
+            }
+            Ok((name, Outcome::Updated)) => {
+                changed += 1;
+                println!("{} {name} ({source})", "Updated:".yellow());
+            }
+            Ok((_, Outcome::AlreadyRegistered)) => {}
+            Err(e) => println!("{} {source}: {e:#}", "Skipped:".red()),
+        }
+    }
+    if found == 0 {
+        println!("No plugins found matching \"{pattern}\"");
+    }
+
+    // Stale entries are only reported; dropping one is up to `cforge remove plugin`
+    let root = Path::new(".");
+    for entry in &marketplace.plugins {
+        if entry.source.local_path(root).is_some_and(|p| !p.exists()) {
+            println!(
+                "{} '{}' points at \"{}\", which does not exist (run `cforge remove plugin {}`)",
+                "Stale:".red(),
+                entry.name,
+                entry.source,
+                entry.name
+            );
+        }
+    }
+
+    if changed > 0 {
+        marketplace.save(market_manifest)?;
+    }
+    println!("✅ {found} plugin(s) found, {changed} registered or updated.");
     Ok(())
 }
+
+/// Registers a freshly scaffolded plugin with the marketplace at `market_root`.
+pub fn register_new(market_root: &Path, plugin_dir: &Path) -> Result<()> {
+    let market_manifest = market_root.join(".claude-plugin/marketplace.json");
+    let mut marketplace = Marketplace::load(&market_manifest)?;
+    let source = local_source(plugin_dir, market_root);
+    let plugin = PluginManifest::load(&plugin_dir.join(".claude-plugin/plugin.json"))?;
+    if marketplace.find_mut(&plugin.name).is_some() {
+        println!(
+            "⚠️  '{}' is already registered in marketplace.json; left it unchanged.",
+            plugin.name
+        );
+        return Ok(());
+    }
+    marketplace
+        .plugins
+        .push(PluginEntry::new(&plugin, PluginSource::Local(source)));
+    marketplace.save(&market_manifest)?;
+    println!("Updated: {:?}", market_manifest);
+    Ok(())
+}
+
+fn marketplace_manifest() -> Result<&'static Path> {
+    let market_manifest = Path::new(".claude-plugin/marketplace.json");
+    if !market_manifest.exists() {
+        return Err(anyhow!(
+            "❌ No marketplace.json found. Run this from the marketplace root."
+        ));
+    }
+    Ok(market_manifest)
+}
+
+// Adds the plugin at `source` (relative to the marketplace root), or re-syncs
+// its entry when `update` is set
+fn upsert(marketplace: &mut Marketplace, source: &str, update: bool) -> Result<(String, Outcome)> {
+    let source = &local_source(Path::new(source), Path::new("."));
+    let plugin = PluginManifest::load(&Path::new(source).join(".claude-plugin/plugin.json"))?;
+    if plugin.meta.version.is_none() {
+        return Err(anyhow!("Plugin missing 'version' field"));
+    }
+
+    // An entry already pointing at this directory counts too, even if the
+    // plugin has since been renamed
+    let canonical = |p: &Path| p.canonicalize().ok();
+    let here = canonical(Path::new(source));
+    let existing = marketplace.plugins.iter_mut().find(|e| {
+        e.name == plugin.name
+            || e.source
+                .local_path(Path::new("."))
+                .is_some_and(|p| canonical(&p) == here)
+    });
+    let outcome = match existing {
+        Some(_) if !update => Outcome::AlreadyRegistered,
+        Some(existing) => {
+            existing.sync_from(&plugin, PluginSource::Local(source.to_string()));
+            Outcome::Updated
+        }
+        None => {
+            marketplace.plugins.push(PluginEntry::new(
+                &plugin,
+                PluginSource::Local(source.to_string()),
+            ));
+            Outcome::Registered
+        }
+    };
+    Ok((plugin.name, outcome))
+}
+
+// `./`-prefixed path of a plugin relative to the marketplace root, the form
+// marketplace.json sources are written in. Paths outside the root are kept
+fn local_source(path: &Path, market_root: &Path) -> String {
+    let inside = match (path.canonicalize(), market_root.canonicalize()) {
+        (Ok(path), Ok(root)) => path.strip_prefix(root).ok().map(Path::to_path_buf),
+        _ => None,
+    };
+    let path = inside.unwrap_or_else(|| path.to_path_buf());
+    if path.is_absolute() {
+        return path.to_string_lossy().into_owned();
+    }
+    let parts: Vec<_> = path
+        .components()
+        .filter(|c| *c != Component::CurDir)
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect();
+    format!("./{}", parts.join("/"))
+}
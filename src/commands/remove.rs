@@ -0,0 +1,417 @@
+use crate::hooks::{HookEvent, HooksConfig};
+use crate::manifest::{Marketplace, PathList, PathOrInline, PluginManifest};
+use crate::mcp::McpConfig;
+use crate::utils;
+use anyhow::{anyhow, Result};
+use clap::{Subcommand, ValueEnum};
+use colored::*;
+use convert_case::{Case, Casing};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Subcommand)]
+pub enum Target {
+    /// Delete a plugin directory and drop its marketplace entry
+    Plugin { name: String },
+    /// Delete skills/<name>/ (or the legacy skills/<name>.md)
+    Skill { name: String },
+    /// Delete commands/<name>.md
+    Command { name: String },
+    /// Delete agents/<name>.md
+    Agent { name: String },
+    /// Delete a hook script (name or event) and unwire it from hooks.json
+    Hook { name: String },
+    /// Drop a server from .mcp.json (or plugin.json's inline mcpServers)
+    Mcp { name: String },
+}
+
+// A manifest rewritten as part of a removal
+enum Edit {
+    Marketplace(Marketplace),
+    Plugin(Box<PluginManifest>),
+    Hooks(PathBuf, HooksConfig),
+    Mcp(PathBuf, McpConfig),
+}
+
+// Everything a removal touches, shown before anything changes
+#[derive(Default)]
+struct Plan {
+    delete: Vec<PathBuf>,
+    edits: Vec<(PathBuf, String, Edit)>,
+}
+
+pub fn run(plugin_flag: Option<String>, target: Target, dry_run: bool, yes: bool) -> Result<()> {
+    let plan = match target {
+        Target::Plugin { name } => plan_plugin(&std::env::current_dir()?, &name)?,
+        target => {
+            let root = utils::resolve_plugin_root(&plugin_flag)?;
+            println!(
+                "🔧 Targeting plugin: {}",
+                root.file_name().unwrap().to_string_lossy().bold()
+            );
+            plan_component(&root, target)?
+        }
+    };
+
+    for path in &plan.delete {
+        println!("{} {:?}", "Delete:".red(), path);
+    }
+    for (path, summary, _) in &plan.edits {
+        println!("{} {:?} ({summary})", "Update:".yellow(), path);
+    }
+
+    if dry_run {
+        println!("\nDry run: nothing was changed.");
+        return Ok(());
+    }
+    if !yes && !utils::confirm("Proceed?", "--yes")? {
+        println!("Aborted.");
+        return Ok(());
+    }
+
+    for path in &plan.delete {
+        if path.is_dir() {
+            fs::remove_dir_all(path)?;
+        } else {
+            fs::remove_file(path)?;
+        }
+    }
+    for (path, _, edit) in plan.edits {
+        match edit {
+            Edit::Marketplace(m) => m.save(&path)?,
+            Edit::Plugin(p) => p.save(&path)?,
+            Edit::Hooks(root, h) => h.save(&root)?,
+            Edit::Mcp(root, m) => m.save(&root)?,
+        }
+    }
+    println!("✅ Removed.");
+    Ok(())
+}
+
+// Plans removing a plugin of the marketplace at `market_root`
+fn plan_plugin(market_root: &Path, name: &str) -> Result<Plan> {
+    utils::check_name(name)?;
+    let mut plan = Plan::default();
+    let market_path = market_root.join(".claude-plugin/marketplace.json");
+    let marketplace = match market_path.exists() {
+        true => Some(Marketplace::load(&market_path)?),
+        false => None,
+    };
+
+    // The entry's source says where the plugin lives; the name lookup covers
+    // plugins that were never registered
+    let from_source = marketplace
+        .iter()
+        .flat_map(|m| &m.plugins)
+        .find(|entry| entry.name == name)
+        .and_then(|entry| entry.source.local_path(market_root))
+        .filter(|p| p.join(".claude-plugin/plugin.json").exists());
+    let dir = from_source.or_else(|| {
+        [
+            market_root.join("plugins").join(name),
+            market_root.join(name),
+        ]
+        .into_iter()
+        .find(|p| p.join(".claude-plugin/plugin.json").exists())
+    });
+    if let Some(dir) = &dir {
+        let market_root = market_root.canonicalize()?;
+        let canonical = dir.canonicalize()?;
+        if canonical == market_root || !canonical.starts_with(&market_root) {
+            return Err(anyhow!(
+                "Refusing to delete {dir:?}: it is not a directory inside the marketplace"
+            ));
+        }
+        plan.delete.push(dir.clone());
+    }
+
+    // The entry may outlive its directory, so look it up either way
+    if let Some(mut marketplace) = marketplace {
+        let same_dir = |p: &Path| {
+            let canonical = |p: &Path| p.canonicalize().ok();
+            dir.as_deref().is_some_and(|d| canonical(p) == canonical(d))
+        };
+        let before = marketplace.plugins.len();
+        marketplace.plugins.retain(|entry| {
+            let local = entry.source.local_path(market_root);
+            entry.name != name && !local.is_some_and(|p| same_dir(&p))
+        });
+        if marketplace.plugins.len() < before {
+            plan.edits.push((
+                market_path.clone(),
+                format!("drop marketplace entry '{name}'"),
+                Edit::Marketplace(marketplace),
+            ));
+        }
+    }
+
+    if plan.delete.is_empty() && plan.edits.is_empty() {
+        return Err(anyhow!(
+            "Plugin '{name}' not found in ./plugins or marketplace.json"
+        ));
+    }
+    Ok(plan)
+}
+
+fn plan_component(root: &Path, target: Target) -> Result<Plan> {
+    let mut plan = Plan::default();
+    let manifest_path = root.join(".claude-plugin/plugin.json");
+
+    match target {
+        Target::Plugin { .. } => unreachable!("plugins are planned by plan_plugin"),
+        Target::Skill { name } => {
+            utils::check_name(&name)?;
+            let path = find_existing(root, "skills", &name, |n| {
+                vec![n.to_string(), format!("{n}.md")]
+            })
+            .ok_or_else(|| anyhow!("Skill '{name}' not found in skills/"))?;
+            plan.delete.push(path);
+        }
+        Target::Command { name } => {
+            plan_markdown(root, &manifest_path, "commands", &name, &mut plan)?
+        }
+        Target::Agent { name } => plan_markdown(root, &manifest_path, "agents", &name, &mut plan)?,
+        Target::Hook { name } => {
+            utils::check_name(&name)?;
+            let script = find_existing(root, "hooks", &name, |n| {
+                let mut candidates = vec![n.to_string(), format!("{n}.sh")];
+                if let Ok(event) = HookEvent::from_str(n, true) {
+                    candidates.push(event.script_name());
+                }
+                // hooks.json sits beside the scripts but is never one
+                candidates.retain(|c| c != "hooks.json");
+                candidates
+            })
+            .ok_or_else(|| anyhow!("Hook script '{name}' not found in hooks/"))?;
+
+            let mut config = HooksConfig::load_or_default(root)?;
+            let unwired = config.remove_script(&script, root)?;
+            if unwired > 0 {
+                plan.edits.push((
+                    HooksConfig::path(root),
+                    format!("unwire {unwired} hook(s)"),
+                    Edit::Hooks(root.to_path_buf(), config),
+                ));
+            }
+            plan.delete.push(script);
+        }
+        Target::Mcp { name } => {
+            let mcp_path = McpConfig::path(root);
+            let mut config = McpConfig::load_or_default(root)?;
+            let mut manifest = PluginManifest::load(&manifest_path)?;
+            if config.mcp_servers.remove(&name).is_some() {
+                // A .mcp.json left with nothing in it goes, along with the
+                // plugin.json pointer to it
+                if config.mcp_servers.is_empty() && config.extra.is_empty() {
+                    plan.delete.push(mcp_path.clone());
+                    let points_here = matches!(
+                        &manifest.mcp_servers,
+                        Some(PathOrInline::Path(p)) if root.join(p) == mcp_path
+                    );
+                    if points_here {
+                        manifest.mcp_servers = None;
+                        plan.edits.push((
+                            manifest_path.clone(),
+                            "drop `mcpServers`".to_string(),
+                            Edit::Plugin(Box::new(manifest.clone())),
+                        ));
+                    }
+                } else {
+                    plan.edits.push((
+                        mcp_path,
+                        format!("drop server '{name}'"),
+                        Edit::Mcp(root.to_path_buf(), config),
+                    ));
+                }
+            }
+            if let Some(PathOrInline::Inline(servers)) = &mut manifest.mcp_servers {
+                if servers.remove(&name).is_some() {
+                    plan.edits.push((
+                        manifest_path.clone(),
+                        format!("drop inline server '{name}'"),
+                        Edit::Plugin(Box::new(manifest)),
+                    ));
+                }
+            }
+            if plan.edits.is_empty() && plan.delete.is_empty() {
+                return Err(anyhow!("MCP server '{name}' not found"));
+            }
+        }
+    }
+    Ok(plan)
+}
+
+// Deletes commands/<name>.md or agents/<name>.md and drops any explicit
+// listing of it from plugin.json
+fn plan_markdown(
+    root: &Path,
+    manifest_path: &Path,
+    dir: &str,
+    name: &str,
+    plan: &mut Plan,
+) -> Result<()> {
+    utils::check_name(name)?;
+    let path = find_existing(root, dir, name, |n| vec![format!("{n}.md")])
+        .ok_or_else(|| anyhow!("'{name}' not found in {dir}/"))?;
+
+    let mut manifest = PluginManifest::load(manifest_path)?;
+    let listed = match dir {
+        "commands" => &mut manifest.commands,
+        _ => &mut manifest.agents,
+    };
+    let rel = format!("./{}", utils::relative(&path, root));
+    let mut unlisted = false;
+    *listed = match listed.take() {
+        Some(PathList::One(p)) if p == rel => {
+            unlisted = true;
+            None
+        }
+        Some(PathList::Many(mut paths)) => {
+            let before = paths.len();
+            paths.retain(|p| *p != rel);
+            unlisted = paths.len() < before;
+            Some(PathList::Many(paths))
+        }
+        other => other,
+    };
+    if unlisted {
+        plan.edits.push((
+            manifest_path.to_path_buf(),
+            format!("drop {rel} from `{dir}`"),
+            Edit::Plugin(Box::new(manifest)),
+        ));
+    }
+    plan.delete.push(path);
+    Ok(())
+}
+
+// First candidate file in `dir` that exists, trying the name as given and
+// kebab-cased. Candidates must be direct children of `dir`
+fn find_existing(
+    root: &Path,
+    dir: &str,
+    name: &str,
+    candidates: impl Fn(&str) -> Vec<String>,
+) -> Option<PathBuf> {
+    let dir = root.join(dir);
+    let mut files = candidates(name);
+    files.extend(candidates(&name.to_case(Case::Kebab)));
+    files
+        .into_iter()
+        .filter(|file| {
+            let mut parts = Path::new(file).components();
+            matches!(parts.next(), Some(Component::Normal(_))) && parts.next().is_none()
+        })
+        .map(|file| dir.join(file))
+        .find(|p| p.exists())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A marketplace holding plugin `tools`; returns its root
+    fn marketplace(dir: &Path) -> PathBuf {
+        let root = dir.join("mk");
+        let plugin = root.join("plugins/tools");
+        fs::create_dir_all(plugin.join(".claude-plugin")).unwrap();
+        fs::create_dir_all(plugin.join("skills/x")).unwrap();
+        fs::create_dir_all(plugin.join("hooks")).unwrap();
+        fs::write(
+            plugin.join(".claude-plugin/plugin.json"),
+            r#"{"name": "tools", "version": "1.0.0", "mcpServers": "./.mcp.json"}"#,
+        )
+        .unwrap();
+        fs::write(plugin.join("hooks/hooks.json"), r#"{"hooks": {}}"#).unwrap();
+        fs::write(
+            plugin.join(".mcp.json"),
+            r#"{"mcpServers": {"db": {"command": "db-server"}}}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(root.join(".claude-plugin")).unwrap();
+        fs::write(
+            root.join(".claude-plugin/marketplace.json"),
+            r#"{"name": "mk", "owner": {"name": "o"}, "plugins": [
+                {"name": "tools", "source": "./plugins/tools"}
+            ]}"#,
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn rejects_names_that_are_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = marketplace(dir.path()).join("plugins/tools");
+        fs::create_dir_all(root.join("x")).unwrap();
+        for name in ["../x", "..", "skills/x"] {
+            let target = Target::Skill {
+                name: name.to_string(),
+            };
+            assert!(plan_component(&root, target).is_err(), "{name}");
+        }
+        assert!(plan_plugin(&root, "../tools").is_err());
+    }
+
+    #[test]
+    fn refuses_plugins_sourced_outside_the_marketplace() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = marketplace(dir.path());
+        let outside = dir.path().join("outside");
+        fs::create_dir_all(outside.join(".claude-plugin")).unwrap();
+        fs::write(
+            outside.join(".claude-plugin/plugin.json"),
+            r#"{"name": "outside"}"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join(".claude-plugin/marketplace.json"),
+            r#"{"name": "mk", "owner": {"name": "o"}, "plugins": [
+                {"name": "outside", "source": "../outside"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let err = plan_plugin(&root, "outside").err().unwrap();
+        assert!(err.to_string().contains("not a directory inside"), "{err}");
+        let plan = plan_plugin(&root, "tools").unwrap();
+        assert_eq!(plan.delete, vec![root.join("plugins/tools")]);
+    }
+
+    #[test]
+    fn hooks_json_is_not_a_hook_script() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = marketplace(dir.path()).join("plugins/tools");
+        let target = Target::Hook {
+            name: "hooks.json".to_string(),
+        };
+        assert!(plan_component(&root, target).is_err());
+    }
+
+    #[test]
+    fn removing_the_last_mcp_server_drops_the_file_and_pointer() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = marketplace(dir.path()).join("plugins/tools");
+        let target = Target::Mcp {
+            name: "db".to_string(),
+        };
+        let plan = plan_component(&root, target).unwrap();
+        assert_eq!(plan.delete, vec![McpConfig::path(&root)]);
+        match &plan.edits[..] {
+            [(_, _, Edit::Plugin(manifest))] => assert!(manifest.mcp_servers.is_none()),
+            _ => panic!("expected one plugin.json edit"),
+        }
+
+        fs::write(
+            McpConfig::path(&root),
+            r#"{"mcpServers": {"db": {"command": "a"}, "cache": {"command": "b"}}}"#,
+        )
+        .unwrap();
+        let target = Target::Mcp {
+            name: "db".to_string(),
+        };
+        let plan = plan_component(&root, target).unwrap();
+        assert!(plan.delete.is_empty());
+        assert!(matches!(&plan.edits[..], [(_, _, Edit::Mcp(..))]));
+    }
+}
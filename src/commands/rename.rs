@@ -0,0 +1,475 @@
+use crate::frontmatter;
+use crate::json;
+use crate::manifest::{Marketplace, PathList, PluginManifest, PluginSource};
+use crate::utils;
+use anyhow::{anyhow, Context, Result};
+use clap::Subcommand;
+use colored::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+#[derive(Subcommand)]
+pub enum Target {
+    /// Rename a plugin: directory, plugin.json, marketplace entry and /plugin:command references
+    Plugin { old: String, new: String },
+    /// Rename skills/<old>/ and its frontmatter `name`
+    Skill { old: String, new: String },
+    /// Rename commands/<old>.md and every /old reference to it
+    Command { old: String, new: String },
+    /// Rename agents/<old>.md
+    Agent { old: String, new: String },
+}
+
+// Every change a rename makes, computed up front so it can be applied (and
+// rolled back) as a unit
+#[derive(Default)]
+struct Plan {
+    edits: Vec<(PathBuf, String)>,
+    moves: Vec<(PathBuf, PathBuf)>,
+}
+
+impl Plan {
+    // Queues new content for `path`, building on any edit already queued
+    fn edit(&mut self, path: &Path, f: impl FnOnce(&str) -> Option<String>) -> Result<()> {
+        let current = match self.edits.iter().find(|(p, _)| p == path) {
+            Some((_, content)) => content.clone(),
+            None => fs::read_to_string(path).with_context(|| format!("Failed to read {path:?}"))?,
+        };
+        if let Some(updated) = f(&current).filter(|u| *u != current) {
+            match self.edits.iter_mut().find(|(p, _)| p == path) {
+                Some((_, content)) => *content = updated,
+                None => self.edits.push((path.to_path_buf(), updated)),
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn run(plugin_flag: Option<String>, target: Target, dry_run: bool) -> Result<()> {
+    let plan = match target {
+        Target::Plugin { old, new } => {
+            utils::check_name(&old)?;
+            let dir = utils::resolve_plugin_root(&Some(old))?;
+            plan_plugin(&dir, &utils::slug(&new)?)?
+        }
+        target => {
+            let root = utils::resolve_plugin_root(&plugin_flag)?;
+            println!(
+                "🔧 Targeting plugin: {}",
+                root.file_name().unwrap().to_string_lossy().bold()
+            );
+            plan_component(&root, target)?
+        }
+    };
+
+    for (from, to) in &plan.moves {
+        println!("{} {:?} → {:?}", "Rename:".cyan(), from, to);
+    }
+    for (path, _) in &plan.edits {
+        println!("{} {:?}", "Update:".yellow(), path);
+    }
+    if dry_run {
+        println!("\nDry run: nothing was changed.");
+        return Ok(());
+    }
+
+    apply(plan)?;
+    println!("✅ Renamed.");
+    Ok(())
+}
+
+fn plan_plugin(dir: &Path, new: &str) -> Result<Plan> {
+    let mut plan = Plan::default();
+    let dir = dir.to_path_buf();
+    let manifest_path = dir.join(".claude-plugin/plugin.json");
+    let mut manifest = PluginManifest::load(&manifest_path)?;
+    let old = std::mem::replace(&mut manifest.name, new.to_string());
+    let value = serde_json::to_value(&manifest)?;
+    plan.edit(&manifest_path, |c| Some(json::render(c, &value)))?;
+
+    let readme = dir.join("README.md");
+    if readme.is_file() {
+        plan.edit(&readme, |c| replace_heading(c, &old, new))?;
+    }
+
+    let market_root = utils::find_marketplace_root(&dir);
+    let new_dir = match dir.file_name().is_some_and(|n| n == old.as_str()) {
+        true => dir.with_file_name(new),
+        false => dir.clone(),
+    };
+    if new_dir != dir {
+        ensure_free(&new_dir)?;
+        plan.moves.push((dir.clone(), new_dir.clone()));
+    }
+
+    if let Some(market_root) = &market_root {
+        let market_path = market_root.join(".claude-plugin/marketplace.json");
+        let mut marketplace = Marketplace::load(&market_path)?;
+        let canonical = |p: &Path| p.canonicalize().ok();
+        let points_here = |source: &PluginSource| {
+            source
+                .local_path(market_root)
+                .is_some_and(|p| canonical(&p) == canonical(&dir))
+        };
+        // Another plugin already registered under the new name
+        if let Some(taken) = marketplace
+            .plugins
+            .iter()
+            .find(|e| e.name == new && e.name != old && !points_here(&e.source))
+        {
+            return Err(anyhow!(
+                "marketplace.json already has a plugin named '{}'",
+                taken.name
+            ));
+        }
+        let mut changed = false;
+        for entry in &mut marketplace.plugins {
+            let points_here = points_here(&entry.source);
+            if entry.name != old && !points_here {
+                continue;
+            }
+            entry.name = new.to_string();
+            if let PluginSource::Local(source) = &mut entry.source {
+                if points_here && new_dir != dir {
+                    *source = Path::new(source.as_str())
+                        .with_file_name(new)
+                        .to_string_lossy()
+                        .into_owned();
+                }
+            }
+            changed = true;
+        }
+        if changed {
+            let value = serde_json::to_value(&marketplace)?;
+            plan.edit(&market_path, |c| Some(json::render(c, &value)))?;
+        }
+    }
+
+    // Plugin commands are invoked as /<plugin>:<command>
+    let scope = market_root.unwrap_or_else(|| dir.clone());
+    for path in markdown_files(&scope) {
+        plan.edit(&path, |c| {
+            replace_slash_refs(c, &format!("{old}:"), &format!("{new}:"), true)
+        })?;
+    }
+    Ok(plan)
+}
+
+fn plan_component(root: &Path, target: Target) -> Result<Plan> {
+    let mut plan = Plan::default();
+    match target {
+        Target::Plugin { .. } => unreachable!("plugins are planned by plan_plugin"),
+        Target::Skill { old, new } => {
+            utils::check_name(&old)?;
+            let new = utils::slug(&new)?;
+            let skills = root.join("skills");
+            let (from, to, file) = if skills.join(&old).is_dir() {
+                let from = skills.join(&old);
+                (from.clone(), skills.join(&new), from.join("SKILL.md"))
+            } else if skills.join(format!("{old}.md")).is_file() {
+                let from = skills.join(format!("{old}.md"));
+                (from.clone(), skills.join(format!("{new}.md")), from)
+            } else {
+                return Err(anyhow!("Skill '{old}' not found in skills/"));
+            };
+            ensure_free(&to)?;
+            plan.edit(&file, |c| frontmatter::replace_field(c, "name", &new))?;
+            plan.moves.push((from, to));
+        }
+        Target::Command { old, new } => {
+            let new = utils::slug(&new)?;
+            let (from, to) = markdown_move(root, "commands", &old, &new)?;
+            list_rename(&mut plan, root, "commands", &from, &to)?;
+            // Both the bare /old and the namespaced /<plugin>:old spellings
+            let plugin = PluginManifest::load(&root.join(".claude-plugin/plugin.json"))?.name;
+            let scope = utils::find_marketplace_root(root).unwrap_or_else(|| root.to_path_buf());
+            let (namespaced_old, namespaced_new) =
+                (format!("{plugin}:{old}"), format!("{plugin}:{new}"));
+            for path in markdown_files(&scope) {
+                plan.edit(&path, |c| {
+                    let namespaced = replace_slash_refs(c, &namespaced_old, &namespaced_new, false);
+                    let c = namespaced.as_deref().unwrap_or(c);
+                    // A bare /old only resolves inside its own plugin
+                    match path.starts_with(root) {
+                        true => replace_slash_refs(c, &old, &new, false).or(namespaced),
+                        false => namespaced,
+                    }
+                })?;
+            }
+            plan.moves.push((from, to));
+        }
+        Target::Agent { old, new } => {
+            let new = utils::slug(&new)?;
+            let (from, to) = markdown_move(root, "agents", &old, &new)?;
+            list_rename(&mut plan, root, "agents", &from, &to)?;
+            plan.edit(&from, |c| frontmatter::replace_field(c, "name", &new))?;
+            plan.moves.push((from, to));
+        }
+    }
+    Ok(plan)
+}
+
+fn markdown_move(root: &Path, dir: &str, old: &str, new: &str) -> Result<(PathBuf, PathBuf)> {
+    utils::check_name(old)?;
+    let from = root.join(dir).join(format!("{old}.md"));
+    if !from.is_file() {
+        return Err(anyhow!("'{old}' not found in {dir}/"));
+    }
+    let to = root.join(dir).join(format!("{new}.md"));
+    ensure_free(&to)?;
+    Ok((from, to))
+}
+
+// Follows the move in plugin.json's explicit `commands`/`agents` listing
+fn list_rename(plan: &mut Plan, root: &Path, key: &str, from: &Path, to: &Path) -> Result<()> {
+    let manifest_path = root.join(".claude-plugin/plugin.json");
+    let mut manifest = PluginManifest::load(&manifest_path)?;
+    let listed = match key {
+        "commands" => &mut manifest.commands,
+        _ => &mut manifest.agents,
+    };
+    let (from, to) = (
+        format!("./{}", utils::relative(from, root)),
+        format!("./{}", utils::relative(to, root)),
+    );
+    let mut changed = false;
+    let mut rename = |p: &mut String| {
+        if *p == from {
+            *p = to.clone();
+            changed = true;
+        }
+    };
+    match listed {
+        Some(PathList::One(p)) => rename(p),
+        Some(PathList::Many(paths)) => paths.iter_mut().for_each(rename),
+        None => {}
+    }
+    if changed {
+        let value = serde_json::to_value(&manifest)?;
+        plan.edit(&manifest_path, |c| Some(json::render(c, &value)))?;
+    }
+    Ok(())
+}
+
+fn ensure_free(path: &Path) -> Result<()> {
+    match path.exists() {
+        true => Err(anyhow!("{path:?} already exists")),
+        false => Ok(()),
+    }
+}
+
+// Markdown files under `root`, skipping hidden directories
+fn markdown_files(root: &Path) -> Vec<PathBuf> {
+    WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().is_some_and(|x| x == "md"))
+        .map(|e| e.into_path())
+        .collect()
+}
+
+// Replaces `/old` with `/new` where it reads as a slash command: not part of a
+// path or longer name. With `prefix`, `old` only has to start the command
+fn replace_slash_refs(text: &str, old: &str, new: &str, prefix: bool) -> Option<String> {
+    let needle = format!("/{old}");
+    let is_name = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut changed = false;
+    while let Some(i) = rest.find(&needle) {
+        let before = rest[..i].chars().last().or_else(|| out.chars().last());
+        let after = rest[i + needle.len()..].chars().next();
+        let standalone = !before.is_some_and(|c| is_name(c) || "/.:".contains(c))
+            && (prefix || !after.is_some_and(is_name));
+
+        out.push_str(&rest[..i]);
+        if standalone {
+            out.push('/');
+            out.push_str(new);
+            changed = true;
+        } else {
+            out.push_str(&needle);
+        }
+        rest = &rest[i + needle.len()..];
+    }
+    out.push_str(rest);
+    changed.then_some(out)
+}
+
+// `# old` as the first line becomes `# new`
+fn replace_heading(content: &str, old: &str, new: &str) -> Option<String> {
+    let (first, rest) = content.split_once('\n').unwrap_or((content, ""));
+    let newline = if content.contains('\n') { "\n" } else { "" };
+    (first.trim() == format!("# {old}")).then(|| format!("# {new}{newline}{rest}"))
+}
+
+// Writes every edit, then performs the moves; on failure, undoes what was done
+fn apply(plan: Plan) -> Result<()> {
+    enum Undo {
+        Write(PathBuf, String),
+        Move(PathBuf, PathBuf),
+    }
+    let mut undo = Vec::new();
+
+    let mut run = || -> Result<()> {
+        for (path, content) in &plan.edits {
+            let original = fs::read_to_string(path)?;
+            json::write_atomic(path, content)?;
+            undo.push(Undo::Write(path.clone(), original));
+        }
+        for (from, to) in &plan.moves {
+            fs::rename(from, to).with_context(|| format!("Failed to move {from:?}"))?;
+            undo.push(Undo::Move(to.clone(), from.clone()));
+        }
+        Ok(())
+    };
+
+    if let Err(e) = run() {
+        for step in undo.into_iter().rev() {
+            let _ = match step {
+                Undo::Write(path, original) => fs::write(path, original),
+                Undo::Move(from, to) => fs::rename(from, to),
+            };
+        }
+        return Err(e.context("Rename failed; changes were rolled back"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A marketplace with plugins `tools` and `other`; returns its root
+    fn marketplace(dir: &Path) -> PathBuf {
+        let root = dir.join("mk");
+        for name in ["tools", "other"] {
+            let plugin = root.join("plugins").join(name);
+            fs::create_dir_all(plugin.join(".claude-plugin")).unwrap();
+            fs::create_dir_all(plugin.join("commands")).unwrap();
+            fs::write(
+                plugin.join(".claude-plugin/plugin.json"),
+                format!(r#"{{"name": "{name}", "version": "1.0.0"}}"#),
+            )
+            .unwrap();
+        }
+        fs::write(
+            root.join("plugins/tools/commands/deploy.md"),
+            "---\ndescription: Deploy\n---\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join(".claude-plugin")).unwrap();
+        fs::write(
+            root.join(".claude-plugin/marketplace.json"),
+            r#"{"name": "mk", "owner": {"name": "o"}, "plugins": [
+                {"name": "tools", "source": "./plugins/tools"},
+                {"name": "other", "source": "./plugins/other"}
+            ]}"#,
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn rejects_names_that_are_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = marketplace(dir.path()).join("plugins/tools");
+        fs::create_dir_all(root.join("skills")).unwrap();
+        for target in [
+            Target::Skill {
+                old: "../commands/deploy".into(),
+                new: "x".into(),
+            },
+            Target::Command {
+                old: "../commands/deploy".into(),
+                new: "x".into(),
+            },
+            Target::Agent {
+                old: "..".into(),
+                new: "x".into(),
+            },
+        ] {
+            let err = plan_component(&root, target).err().unwrap();
+            assert!(err.to_string().contains("not a path"), "{err}");
+        }
+        assert!(root.join("commands/deploy.md").is_file());
+    }
+
+    #[test]
+    fn refuses_existing_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = marketplace(dir.path());
+        let tools = root.join("plugins/tools");
+        fs::write(
+            tools.join("commands/ship.md"),
+            "---\ndescription: Ship\n---\n",
+        )
+        .unwrap();
+        let target = Target::Command {
+            old: "deploy".into(),
+            new: "ship".into(),
+        };
+        assert!(plan_component(&tools, target).is_err());
+
+        // `other` is both a directory and a marketplace entry
+        assert!(plan_plugin(&tools, "other").is_err());
+        fs::rename(root.join("plugins/other"), root.join("vendor-other")).unwrap();
+        let err = plan_plugin(&tools, "other").err().unwrap();
+        assert!(err
+            .to_string()
+            .contains("already has a plugin named 'other'"));
+
+        assert!(plan_plugin(&tools, "renamed").is_ok());
+    }
+
+    #[test]
+    fn replaces_standalone_slash_commands() {
+        let text = "Run /deploy or /deploy-all, see docs/deploy and http://x/deploy.\n/deploy";
+        assert_eq!(
+            replace_slash_refs(text, "deploy", "ship", false).unwrap(),
+            "Run /ship or /deploy-all, see docs/deploy and http://x/deploy.\n/ship"
+        );
+        assert_eq!(
+            replace_slash_refs("/deployed", "deploy", "ship", false),
+            None
+        );
+        assert_eq!(
+            replace_slash_refs("Use /tools:deploy.", "tools:", "kit:", true).unwrap(),
+            "Use /kit:deploy."
+        );
+    }
+
+    #[test]
+    fn replaces_only_a_matching_first_heading() {
+        assert_eq!(
+            replace_heading("# tools\n\nAbout tools\n", "tools", "kit").unwrap(),
+            "# kit\n\nAbout tools\n"
+        );
+        assert_eq!(replace_heading("# tools", "tools", "kit").unwrap(), "# kit");
+        assert_eq!(replace_heading("# tools-x\n", "tools", "kit"), None);
+        assert_eq!(replace_heading("intro\n# tools\n", "tools", "kit"), None);
+    }
+
+    #[test]
+    fn failed_apply_rolls_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let (edited, moved) = (dir.path().join("a.md"), dir.path().join("b.md"));
+        fs::write(&edited, "before").unwrap();
+        fs::write(&moved, "b").unwrap();
+        let plan = Plan {
+            edits: vec![(edited.clone(), "after".into())],
+            moves: vec![
+                (moved.clone(), dir.path().join("c.md")),
+                (dir.path().join("missing.md"), dir.path().join("d.md")),
+            ],
+        };
+        let err = apply(plan).unwrap_err();
+        assert!(err.to_string().contains("rolled back"));
+        assert_eq!(fs::read_to_string(&edited).unwrap(), "before");
+        assert!(moved.is_file() && !dir.path().join("c.md").exists());
+    }
+}
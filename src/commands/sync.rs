@@ -0,0 +1,55 @@
+use crate::manifest::{Marketplace, PluginManifest};
+use crate::utils;
+use anyhow::{anyhow, Result};
+use colored::*;
+
+pub fn run(write: bool) -> Result<()> {
+    let root = utils::find_marketplace_root(&std::env::current_dir()?)
+        .ok_or_else(|| anyhow!("No marketplace.json found in this directory or its parents"))?;
+    let market_path = root.join(".claude-plugin/marketplace.json");
+    let mut marketplace = Marketplace::load(&market_path)?;
+
+    let mut drifted = 0;
+    for entry in &mut marketplace.plugins {
+        // Remote plugins have no local plugin.json to compare against
+        let Some(dir) = entry.source.local_path(&root) else {
+            continue;
+        };
+        let manifest_path = dir.join(".claude-plugin/plugin.json");
+        let plugin = match PluginManifest::load(&manifest_path) {
+            Ok(plugin) => plugin,
+            Err(e) => {
+                println!("{} {}: {e:#}", "Skipped:".yellow(), entry.name);
+                continue;
+            }
+        };
+
+        let drift = entry.drift(&plugin);
+        if drift.is_empty() {
+            continue;
+        }
+        drifted += 1;
+        println!("{} ({})", entry.name.bold(), entry.source);
+        for d in &drift {
+            println!("  {d}");
+        }
+        if write {
+            entry.sync_from(&plugin, entry.source.clone());
+        }
+    }
+
+    match (drifted, write) {
+        (0, _) => println!("✅ Marketplace entries match their plugin.json files."),
+        (_, true) => {
+            marketplace.save(&market_path)?;
+            println!("Updated: {:?}", market_path);
+            println!("🔄 Synced {drifted} plugin(s) from their plugin.json.");
+        }
+        (_, false) => {
+            return Err(anyhow!(
+                "{drifted} plugin(s) drifted from their plugin.json; run `cforge sync --write` to update marketplace.json"
+            ))
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use crate::json;
+use crate::kits;
+use crate::templates::{self, Templates, BUILTIN};
+use crate::utils;
+use anyhow::{anyhow, Result};
+use clap::Subcommand;
+use colored::*;
+
+#[derive(Subcommand)]
+pub enum Action {
+    /// List every template and where it would be loaded from here
+    List,
+    /// Print a template's source as cforge would use it here
+    Show {
+        name: String,
+        /// Print the built-in default even if it is overridden
+        #[arg(long)]
+        builtin: bool,
+    },
+    /// Copy built-in templates into .cforge/templates/ to customize them
+    Eject {
+        /// Templates to copy (defaults to all)
+        names: Vec<String>,
+        /// Copy into ~/.config/cforge/templates/ instead of the marketplace
+        #[arg(long)]
+        user: bool,
+        /// Overwrite templates that were already ejected
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+pub fn run(action: Action) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let effective = Templates::discover(&cwd);
+
+    match action {
+        Action::List => {
+            println!(
+                "{:<24} {:<10} {}",
+                "TEMPLATE".bold(),
+                "SOURCE".bold(),
+                "PATH".bold()
+            );
+            println!("{}", "-".repeat(80));
+            for (name, _) in BUILTIN {
+                let (origin, _) = effective.source(name)?;
+                let path = origin.path().map(|p| p.display().to_string());
+                println!(
+                    "{:<24} {:<10} {}",
+                    name,
+                    origin.label(),
+                    path.unwrap_or_default()
+                );
+            }
+            println!(
+                "\nVariables: name, slug, description, plugin, author, author_email, date \
+                 (agent.md adds capabilities, hook.sh adds event)."
+            );
+            println!("Filters: `json` and `yaml` quote a value for JSON or a frontmatter line.");
+
+            println!("\n{}", "Starter kits (new-plugin --template <KIT>):".bold());
+            for (name, summary) in kits::builtin() {
+                println!("  {name:<15} {summary}");
+            }
+            println!(
+                "  A kit directory or git URL is copied as-is; its *.j2 files are rendered as templates."
+            );
+        }
+        Action::Show { name, builtin } => {
+            let templates = match builtin {
+                true => Templates::builtin(),
+                false => effective,
+            };
+            let (_, source) = templates.source(&name)?;
+            print!("{source}");
+        }
+        Action::Eject { names, user, force } => {
+            let dir = match user {
+                true => templates::user_dir()
+                    .ok_or_else(|| anyhow!("Cannot locate ~/.config (HOME is not set)"))?,
+                false => utils::find_marketplace_root(&cwd)
+                    .ok_or_else(|| {
+                        anyhow!("Not in a marketplace; pass --user to eject into ~/.config/cforge/templates")
+                    })?
+                    .join(templates::PROJECT_DIR),
+            };
+            let names: Vec<&str> = match names.is_empty() {
+                true => BUILTIN.iter().map(|(n, _)| *n).collect(),
+                false => names.iter().map(String::as_str).collect(),
+            };
+
+            utils::create_dir(&dir)?;
+            let builtin = Templates::builtin();
+            for name in names {
+                let (_, source) = builtin.source(name)?;
+                let path = dir.join(name);
+                if force && path.exists() {
+                    json::write_atomic(&path, &source)?;
+                    println!("{} {:?}", "Updated:".yellow(), path);
+                } else {
+                    utils::create_file(&path, &source)?;
+                }
+            }
+            println!("\n✅ Edit the files in {dir:?}; cforge uses them in place of the built-ins.");
+        }
+    }
+    Ok(())
+}
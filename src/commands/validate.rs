@@ -1,56 +1,97 @@
-use anyhow::Result;
+use crate::diagnostics::{self, Diagnostic};
+use crate::frontmatter;
+use crate::hooks::{self, HookEvent, HooksConfig};
+use crate::lint::{self, LintConfig};
+use crate::manifest::{
+    is_kebab_case, pointer, Marketplace, MarketplaceError, PluginManifest, PluginSource, Violation,
+};
+use crate::mcp::{self, McpConfig};
+use crate::utils::{relative, sorted_entries};
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
 use colored::*;
-use serde::Deserialize;
+use serde_json::{Map, Value};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-// Marketplace schema
-#[derive(Deserialize)]
-struct Marketplace {
-    #[allow(dead_code)]
-    name: String,
-    #[allow(dead_code)]
-    description: Option<String>,
-    #[allow(dead_code)]
-    owner: Option<Owner>,
-    plugins: Vec<PluginEntry>,
-}
-
-#[derive(Deserialize)]
-struct Owner {
-    #[allow(dead_code)]
-    name: String,
-    #[allow(dead_code)]
-    email: Option<String>,
-}
-
-#[derive(Deserialize)]
-struct PluginEntry {
-    name: String,
-    version: String,
-    source: String,
-    #[allow(dead_code)]
-    description: Option<String>,
-}
-
-// Main entry point with auto-detection
-pub fn validate(path_opt: Option<String>, fail_fast: bool) -> Result<()> {
+/// Output format for validation results.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Sarif,
+}
+
+/// Exit code when validation ran and found errors.
+const EXIT_INVALID: u8 = 1;
+/// Exit code when validation itself could not run (I/O failure, bad path, ...).
+const EXIT_CRASHED: u8 = 2;
+
+// Main entry point: maps the outcome onto distinct exit codes
+pub fn run(path_opt: Option<String>, fail_fast: bool, format: Format, strict: bool) -> ExitCode {
+    match validate(path_opt, fail_fast, format, strict) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(EXIT_INVALID),
+        Err(e) => {
+            eprintln!("Error: {e:?}");
+            ExitCode::from(EXIT_CRASHED)
+        }
+    }
+}
+
+// Validates with auto-detection; returns whether the target is valid
+fn validate(
+    path_opt: Option<String>,
+    fail_fast: bool,
+    format: Format,
+    strict: bool,
+) -> Result<bool> {
     let root = resolve_path(path_opt)?;
+    let text = format == Format::Text;
+    let mut config = LintConfig::discover(&root)?;
+    config.strict = strict;
 
-    if is_marketplace(&root)? {
-        validate_marketplace(&root, fail_fast)
+    let diagnostics = if is_marketplace(&root)? {
+        validate_marketplace(&root, &config, fail_fast, text)?
     } else {
-        validate_plugin(&root)
+        if text {
+            println!("Validating plugin at {:?}...", root);
+        }
+        let mut diagnostics = validate_plugin(&root, &root)?;
+        config.apply(&mut diagnostics);
+        if text {
+            print_plugin_result(&diagnostics);
+        }
+        diagnostics
+    };
+
+    match format {
+        Format::Text => {}
+        Format::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&diagnostics::to_json(&diagnostics))?
+        ),
+        Format::Sarif => println!(
+            "{}",
+            serde_json::to_string_pretty(&diagnostics::to_sarif(&diagnostics))?
+        ),
     }
+
+    Ok(!diagnostics.iter().any(Diagnostic::is_error))
 }
 
-// Helper to resolve path
+// Helper to resolve path; a path that is not a directory is an error, not
+// an invalid plugin
 fn resolve_path(path_opt: Option<String>) -> Result<PathBuf> {
-    let current = std::env::current_dir()?;
-    Ok(match path_opt {
+    let path = match path_opt {
         Some(p) => Path::new(&p).to_path_buf(),
-        None => current,
-    })
+        None => std::env::current_dir()?,
+    };
+    if !path.is_dir() {
+        return Err(anyhow!("{path:?} is not a directory"));
+    }
+    Ok(path)
 }
 
 // Check if directory is a marketplace
@@ -58,123 +99,708 @@ fn is_marketplace(path: &Path) -> Result<bool> {
     Ok(path.join(".claude-plugin/marketplace.json").exists())
 }
 
-// Validate single plugin
-fn validate_plugin(root: &Path) -> Result<()> {
-    println!("Validating plugin at {:?}...", root);
-    let mut errors: Vec<String> = Vec::new();
+// Validate single plugin; file names are reported relative to `base`
+fn validate_plugin(root: &Path, base: &Path) -> Result<Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+    let rel = |p: &Path| relative(p, base);
 
     // 2. Check Manifest
     let manifest_path = root.join(".claude-plugin/plugin.json");
+    let mut manifest = None;
     if !manifest_path.exists() {
-        errors.push("❌ Missing .claude-plugin/plugin.json".to_string());
+        diagnostics.push(Diagnostic::error(
+            "missing-manifest",
+            rel(&manifest_path),
+            "missing .claude-plugin/plugin.json",
+        ));
     } else {
         let content = fs::read_to_string(&manifest_path)?;
-        if serde_json::from_str::<serde_json::Value>(&content).is_err() {
-            errors.push("❌ plugin.json contains invalid JSON".to_string());
+        match serde_json::from_str::<Value>(&content) {
+            Ok(value) => {
+                for v in PluginManifest::check(&value, root) {
+                    let file = rel(&manifest_path);
+                    let d = match v.rule {
+                        "unknown-field" => Diagnostic::warning(v.rule, file, v.message),
+                        _ => Diagnostic::error(v.rule, file, v.message),
+                    };
+                    diagnostics.push(d.with_pointer(v.pointer));
+                }
+                manifest = Some(value);
+            }
+            Err(e) => diagnostics.push(
+                Diagnostic::error(
+                    "invalid-json",
+                    rel(&manifest_path),
+                    format!("invalid JSON: {e}"),
+                )
+                .at(e.line(), e.column()),
+            ),
         }
     }
 
-    // 3. Check Skills (YAML Frontmatter)
-    let skills_dir = root.join("skills");
-    if skills_dir.exists() {
-        for entry in fs::read_dir(skills_dir)? {
-            let entry = entry?;
-            let path = entry.path();
+    // 3. Check Skills: skills/<name>/SKILL.md, or the legacy skills/<name>.md
+    for path in sorted_entries(&root.join("skills"))? {
+        let stem = path.file_stem().unwrap().to_string_lossy().into_owned();
+        if path.is_dir() {
+            let skill_md = path.join("SKILL.md");
+            if !skill_md.exists() {
+                diagnostics.push(Diagnostic::error(
+                    "missing-skill-file",
+                    rel(&path),
+                    "skill directory has no SKILL.md",
+                ));
+                continue;
+            }
+            let frontmatter =
+                check_markdown_component(&skill_md, rel(&skill_md), check_skill, &mut diagnostics)?;
+            let name = frontmatter.as_ref().and_then(|f| f.get("name")?.as_str());
+            if name.is_some_and(|n| n != stem) {
+                diagnostics.push(Diagnostic::error(
+                    "frontmatter-field",
+                    rel(&skill_md),
+                    format!("`name` must match the skill directory \"{stem}\""),
+                ));
+            }
+        } else if path.extension().is_some_and(|ext| ext == "md") {
+            diagnostics.push(Diagnostic::warning(
+                "legacy-skill-layout",
+                rel(&path),
+                format!("legacy flat skill layout; move it to skills/{stem}/SKILL.md"),
+            ));
+            check_markdown_component(&path, rel(&path), check_legacy_skill, &mut diagnostics)?;
+        }
+    }
+
+    // 4. Check Commands and Agents (YAML Frontmatter)
+    let markdown: [(&str, ComponentRules); 2] =
+        [("commands", check_command), ("agents", check_agent)];
+    for (dir, rules) in markdown {
+        for path in sorted_entries(&root.join(dir))? {
             if path.extension().is_some_and(|ext| ext == "md") {
-                let content = fs::read_to_string(&path)?;
-                if !validate_frontmatter(&content) {
-                    let filename = path.file_name().unwrap();
-                    errors.push(format!(
-                        "❌ Skill {filename:?} has invalid YAML frontmatter"
-                    ));
+                check_markdown_component(&path, rel(&path), rules, &mut diagnostics)?;
+            }
+        }
+    }
+
+    // 5. Check Hook scripts: those hooks.json runs directly, and those named
+    // after an event. READMEs and sourced helpers in hooks/ are left alone
+    for path in hooks::scripts(root) {
+        check_hook_script(&path, rel(&path), &mut diagnostics)?;
+    }
+
+    // 6. Check hooks/hooks.json wiring
+    let hooks_path = HooksConfig::path(root);
+    if hooks_path.exists() {
+        check_hooks_config(root, &hooks_path, rel(&hooks_path), &mut diagnostics)?;
+    }
+
+    // 7. Check MCP servers: .mcp.json, plus whatever plugin.json's mcpServers points at
+    let mut mcp_files = vec![McpConfig::path(root)];
+    match manifest.as_ref().and_then(|m| m.get("mcpServers")) {
+        Some(Value::String(path)) => {
+            let path = root.join(path);
+            if path != mcp_files[0] {
+                mcp_files.push(path);
+            }
+        }
+        Some(servers @ Value::Object(_)) => {
+            let file = rel(&manifest_path);
+            for v in mcp::check_servers(servers, "/mcpServers", root) {
+                diagnostics.push(mcp_diagnostic(v, file.clone()));
+            }
+        }
+        _ => {}
+    }
+    for path in mcp_files.iter().filter(|p| p.is_file()) {
+        let content = fs::read_to_string(path)?;
+        match serde_json::from_str::<Value>(&content) {
+            Ok(value) => {
+                for v in mcp::check_file(&value, root) {
+                    diagnostics.push(mcp_diagnostic(v, rel(path)));
                 }
             }
+            Err(e) => diagnostics.push(
+                Diagnostic::error("mcp-config", rel(path), format!("invalid JSON: {e}"))
+                    .at(e.line(), e.column()),
+            ),
         }
     }
 
-    // 4. Report
-    if errors.is_empty() {
-        println!("{}", "✅ Plugin structure is VALID.".green().bold());
-        Ok(())
+    // 8. Lint rules (quality checks beyond the schema)
+    diagnostics.extend(lint::check_plugin(root, base)?);
+
+    Ok(diagnostics)
+}
+
+// Relative paths still work when Claude happens to run from the plugin root
+fn mcp_diagnostic(v: Violation, file: String) -> Diagnostic {
+    let d = match v.rule {
+        "mcp-relative-path" => Diagnostic::warning(v.rule, file, v.message),
+        _ => Diagnostic::error(v.rule, file, v.message),
+    };
+    d.with_pointer(v.pointer)
+}
+
+fn print_plugin_result(diagnostics: &[Diagnostic]) {
+    print_result(diagnostics, "✅ Plugin structure is VALID.");
+}
+
+fn print_result(diagnostics: &[Diagnostic], success: &str) {
+    diagnostics::print_text(diagnostics);
+    if diagnostics.iter().any(Diagnostic::is_error) {
+        eprintln!("Error: Validation failed");
     } else {
-        for e in errors {
-            println!("{}", e.red());
-        }
-        Err(anyhow::anyhow!("Validation failed"))
+        println!("{}", success.green().bold());
     }
 }
 
 // Validate entire marketplace
-fn validate_marketplace(root: &Path, fail_fast: bool) -> Result<()> {
-    println!("Validating marketplace at {:?}...", root);
+fn validate_marketplace(
+    root: &Path,
+    config: &LintConfig,
+    fail_fast: bool,
+    text: bool,
+) -> Result<Vec<Diagnostic>> {
+    if text {
+        println!("Validating marketplace at {:?}...", root);
+    }
+    let mut diagnostics = Vec::new();
 
     // Parse marketplace.json
     let marketplace_path = root.join(".claude-plugin/marketplace.json");
+    let marketplace_file = relative(&marketplace_path, root);
     let content = fs::read_to_string(&marketplace_path)?;
-    let marketplace: Marketplace = serde_json::from_str(&content)
-        .map_err(|e| anyhow::anyhow!("Invalid marketplace.json: {}", e))?;
+    let marketplace = match Marketplace::parse(&content) {
+        Ok(m) => m,
+        Err(e) => {
+            let d = match e {
+                MarketplaceError::Syntax(e) => Diagnostic::error(
+                    "invalid-marketplace",
+                    marketplace_file,
+                    format!("invalid marketplace.json: {e}"),
+                )
+                .at(e.line(), e.column()),
+                MarketplaceError::Entry { pointer, message } => Diagnostic::error(
+                    "invalid-marketplace",
+                    marketplace_file,
+                    format!("invalid marketplace.json: {message}"),
+                )
+                .with_pointer(pointer),
+            };
+            if text {
+                diagnostics::print_text(std::slice::from_ref(&d));
+            }
+            diagnostics.push(d);
+            return Ok(diagnostics);
+        }
+    };
 
     let total = marketplace.plugins.len();
-    println!("Found {} plugin(s) in marketplace\n", total);
+    if text {
+        println!("Found {} plugin(s) in marketplace\n", total);
+    }
 
     let mut passed = 0;
     let mut failed = 0;
 
     for (idx, plugin) in marketplace.plugins.iter().enumerate() {
-        println!(
-            "[{}/{}] {} (v{})",
-            idx + 1,
-            total,
-            plugin.name,
-            plugin.version
-        );
+        let version = plugin.meta.version.as_deref().unwrap_or("?");
+        if text {
+            println!("[{}/{}] {} (v{})", idx + 1, total, plugin.name, version);
+        }
+
+        let mut found = Vec::new();
+        if plugin.meta.version.is_none() {
+            found.push(
+                Diagnostic::error(
+                    "invalid-marketplace",
+                    marketplace_file.clone(),
+                    format!("plugin '{}' has no `version`", plugin.name),
+                )
+                .with_pointer(format!("/plugins/{idx}/version")),
+            );
+        }
 
-        // Resolve plugin path
-        let plugin_path = root.join(&plugin.source);
+        // Resolve plugin path; remote sources can only be syntax-checked
+        match plugin.source.local_path(root) {
+            Some(plugin_path) if !plugin_path.exists() => found.push(
+                Diagnostic::error(
+                    "missing-plugin",
+                    marketplace_file.clone(),
+                    format!(
+                        "plugin '{}' points at \"{}\", which does not exist",
+                        plugin.name, plugin.source
+                    ),
+                )
+                .with_pointer(format!("/plugins/{idx}/source")),
+            ),
+            Some(plugin_path) => {
+                if text {
+                    println!("Validating plugin at {:?}...", plugin_path);
+                }
+                found.extend(validate_plugin(&plugin_path, root)?);
+                let manifest = plugin_path.join(".claude-plugin/plugin.json");
+                if let Ok(manifest) = PluginManifest::load(&manifest) {
+                    for drift in plugin.drift(&manifest) {
+                        found.push(
+                            Diagnostic::warning(
+                                "marketplace-drift",
+                                marketplace_file.clone(),
+                                format!("plugin '{}': {drift}", plugin.name),
+                            )
+                            .with_pointer(format!("/plugins/{idx}/{}", drift.field)),
+                        );
+                    }
+                }
+            }
+            None => {
+                if text {
+                    println!(
+                        "Checking {} source {}...",
+                        plugin.source.kind(),
+                        plugin.source
+                    );
+                }
+                for problem in plugin.source.check_remote() {
+                    found.push(
+                        Diagnostic::error("invalid-source", marketplace_file.clone(), problem)
+                            .with_pointer(format!("/plugins/{idx}/source")),
+                    );
+                }
+            }
+        }
+        config.apply(&mut found);
+        if text {
+            match plugin.source {
+                PluginSource::Local(_) => print_plugin_result(&found),
+                _ => print_result(&found, "✅ Remote source is well-formed (not fetched)."),
+            }
+            println!();
+        }
 
-        if !plugin_path.exists() {
-            println!("  {} Plugin directory not found", "✗".red());
+        let ok = !found.iter().any(Diagnostic::is_error);
+        diagnostics.extend(found);
+        if ok {
+            passed += 1;
+        } else {
             failed += 1;
             if fail_fast {
-                return Err(anyhow::anyhow!("Validation failed for {}", plugin.name));
+                if text {
+                    eprintln!("Error: Validation failed for {}", plugin.name);
+                }
+                return Ok(diagnostics);
+            }
+        }
+    }
+
+    // Marketplace-wide lint rules
+    let mut found = lint::check_marketplace(root, &marketplace)?;
+    config.apply(&mut found);
+    if text && !found.is_empty() {
+        println!("Marketplace checks:");
+        diagnostics::print_text(&found);
+        println!();
+    }
+    let marketplace_ok = !found.iter().any(Diagnostic::is_error);
+    diagnostics.extend(found);
+
+    // Print summary
+    if text {
+        let warnings = diagnostics.iter().filter(|d| !d.is_error()).count();
+        println!(
+            "Marketplace validation: {}/{} plugins valid, {} warning(s)",
+            passed, total, warnings
+        );
+        if !marketplace_ok {
+            eprintln!("Error: marketplace-wide checks failed");
+        }
+        if failed > 0 {
+            eprintln!("Error: {} plugin(s) failed validation", failed);
+        } else if marketplace_ok {
+            println!("{}", "All plugins valid!".green().bold());
+        }
+    }
+
+    Ok(diagnostics)
+}
+
+// Frontmatter rules for a component type; each problem is a standalone message
+type ComponentRules = fn(&Map<String, Value>) -> Vec<String>;
+
+fn check_markdown_component(
+    path: &Path,
+    file: String,
+    rules: ComponentRules,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Result<Option<Map<String, Value>>> {
+    let content = fs::read_to_string(path)?;
+    match frontmatter::parse(&content) {
+        Ok(frontmatter) => {
+            for problem in rules(&frontmatter) {
+                diagnostics.push(Diagnostic::error(
+                    "frontmatter-field",
+                    file.clone(),
+                    problem,
+                ));
             }
-            continue;
+            Ok(Some(frontmatter))
         }
+        Err(e) => {
+            let frame = e.code_frame(&file, &content);
+            diagnostics.push(
+                Diagnostic::error("invalid-frontmatter", file, e.message)
+                    .at(e.line, e.column)
+                    .with_snippet(frame),
+            );
+            Ok(None)
+        }
+    }
+}
+
+fn check_hook_script(path: &Path, file: String, diagnostics: &mut Vec<Diagnostic>) -> Result<()> {
+    if !path.is_file() {
+        return Ok(());
+    }
+
+    let content = fs::read(path)?;
+    if !content.starts_with(b"#!") {
+        diagnostics.push(
+            Diagnostic::error(
+                "hook-shebang",
+                file.clone(),
+                "missing a shebang line (e.g. #!/bin/bash)",
+            )
+            .at(1, 1),
+        );
+    }
+
+    if !is_executable(path)? {
+        diagnostics.push(Diagnostic::error(
+            "hook-executable",
+            file,
+            "not executable (run chmod +x)",
+        ));
+    }
+    Ok(())
+}
 
-        // Validate the plugin
-        match validate_plugin(&plugin_path) {
-            Ok(_) => {
-                passed += 1;
+#[cfg(unix)]
+fn is_executable(path: &Path) -> Result<bool> {
+    use std::os::unix::fs::PermissionsExt;
+    Ok(fs::metadata(path)?.permissions().mode() & 0o111 != 0)
+}
+
+// Windows has no executable bit; anything that exists can be run
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> Result<bool> {
+    Ok(true)
+}
+
+fn check_hooks_config(
+    root: &Path,
+    path: &Path,
+    file: String,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Result<()> {
+    let content = fs::read_to_string(path)?;
+    let config: HooksConfig = match serde_json::from_str(&content) {
+        Ok(config) => config,
+        Err(e) => {
+            diagnostics.push(
+                Diagnostic::error("hook-config", file, format!("invalid hooks.json: {e}"))
+                    .at(e.line(), e.column()),
+            );
+            return Ok(());
+        }
+    };
+
+    for event in config.hooks.keys() {
+        let event_ptr = pointer(&["hooks", event]);
+        if HookEvent::from_name(event).is_none() {
+            diagnostics.push(
+                Diagnostic::error(
+                    "hook-config",
+                    file.clone(),
+                    format!("unknown hook event \"{event}\""),
+                )
+                .with_pointer(event_ptr.clone()),
+            );
+        }
+        let groups = match config.groups(event) {
+            Ok(groups) => groups,
+            Err(e) => {
+                diagnostics.push(
+                    Diagnostic::error("hook-config", file.clone(), format!("{e:#}"))
+                        .with_pointer(event_ptr),
+                );
+                continue;
             }
-            Err(_) => {
-                failed += 1;
-                if fail_fast {
-                    return Err(anyhow::anyhow!("Validation failed for {}", plugin.name));
+        };
+
+        for (gi, group) in groups.iter().enumerate() {
+            for (hi, hook) in group.hooks.iter().enumerate() {
+                let hook_ptr = format!("{event_ptr}/{gi}/hooks/{hi}");
+                let command = match (hook.kind.as_str(), &hook.command) {
+                    ("command", Some(command)) => command,
+                    ("command", None) => {
+                        diagnostics.push(
+                            Diagnostic::error(
+                                "hook-config",
+                                file.clone(),
+                                "command hook has no `command`",
+                            )
+                            .with_pointer(hook_ptr),
+                        );
+                        continue;
+                    }
+                    ("prompt", _) => continue,
+                    (kind, _) => {
+                        diagnostics.push(
+                            Diagnostic::error(
+                                "hook-config",
+                                file.clone(),
+                                format!(
+                                    "unknown hook type \"{kind}\" (expected command or prompt)"
+                                ),
+                            )
+                            .with_pointer(format!("{hook_ptr}/type")),
+                        );
+                        continue;
+                    }
+                };
+
+                for (script, direct) in hooks::referenced_scripts(command, root) {
+                    let shown = relative(&script, root);
+                    if !script.is_file() {
+                        diagnostics.push(
+                            Diagnostic::error(
+                                "missing-hook-script",
+                                file.clone(),
+                                format!("runs {shown}, which does not exist"),
+                            )
+                            .with_pointer(format!("{hook_ptr}/command")),
+                        );
+                    } else if direct && !is_executable(&script)? {
+                        diagnostics.push(
+                            Diagnostic::error(
+                                "hook-executable",
+                                file.clone(),
+                                format!("runs {shown}, which is not executable (run chmod +x)"),
+                            )
+                            .with_pointer(format!("{hook_ptr}/command")),
+                        );
+                    }
                 }
             }
         }
-        println!();
     }
+    Ok(())
+}
 
-    // Print summary
-    println!("Marketplace validation: {}/{} plugins valid", passed, total);
+fn check_skill(frontmatter: &Map<String, Value>) -> Vec<String> {
+    let mut problems = Vec::new();
+    match frontmatter.get("name") {
+        Some(Value::String(n)) if is_kebab_case(n) => {}
+        Some(Value::String(n)) => problems.push(format!("`name` \"{n}\" is not kebab-case")),
+        Some(_) => problems.push("non-string `name`".to_string()),
+        None => problems.push("missing `name` in frontmatter".to_string()),
+    }
+    problems.extend(check_description(frontmatter));
+    problems
+}
 
-    if failed > 0 {
-        Err(anyhow::anyhow!("{} plugin(s) failed validation", failed))
-    } else {
-        println!("{}", "All plugins valid!".green().bold());
-        Ok(())
+fn check_legacy_skill(_frontmatter: &Map<String, Value>) -> Vec<String> {
+    Vec::new()
+}
+
+fn check_command(frontmatter: &Map<String, Value>) -> Vec<String> {
+    check_description(frontmatter).into_iter().collect()
+}
+
+fn check_agent(frontmatter: &Map<String, Value>) -> Vec<String> {
+    let mut problems: Vec<String> = check_description(frontmatter).into_iter().collect();
+    for key in ["capabilities", "tools"] {
+        let well_formed = match frontmatter.get(key) {
+            None => true,
+            Some(Value::Array(items)) => items
+                .iter()
+                .all(|i| i.as_str().is_some_and(|s| !s.trim().is_empty())),
+            // `tools` is commonly written as a comma-separated string
+            Some(Value::String(s)) if key == "tools" => s.split(',').all(|t| !t.trim().is_empty()),
+            Some(_) => false,
+        };
+        if !well_formed {
+            problems.push(format!(
+                "malformed `{key}` list (expected non-empty names, e.g. [a, b])"
+            ));
+        }
     }
+    problems
 }
 
-fn validate_frontmatter(content: &str) -> bool {
-    if !content.starts_with("---") {
-        return false;
+fn check_description(frontmatter: &Map<String, Value>) -> Option<String> {
+    match frontmatter.get("description") {
+        Some(Value::String(s)) if !s.trim().is_empty() => None,
+        Some(Value::String(_)) => Some("empty `description`".to_string()),
+        Some(_) => Some("non-string `description`".to_string()),
+        None => Some("missing `description` in frontmatter".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kits;
+    use crate::templates::Templates;
+    use crate::utils;
+    use quickcheck::{Arbitrary, Gen, QuickCheck, TestResult};
+
+    // Text mixing arbitrary strings with fragments JSON and YAML treat specially
+    #[derive(Clone, Debug)]
+    struct Text(String);
+
+    impl Arbitrary for Text {
+        fn arbitrary(g: &mut Gen) -> Self {
+            const TRICKY: &[&str] = &[
+                "\"", "\\", "\n", "\r\n", "\t", ": ", " #", "[", "]", "{", "}", "- ", "---", "'",
+                "yes", "null", "1.0", "*", "&", "!", "%", "@", "`", "|", ">", " ", "é", "🔧",
+            ];
+            let parts = usize::arbitrary(g) % 8;
+            let text = (0..parts)
+                .map(|_| match bool::arbitrary(g) {
+                    true => g.choose(TRICKY).unwrap().to_string(),
+                    false => String::arbitrary(g),
+                })
+                .collect();
+            Text(text)
+        }
+    }
+
+    // Writes a plugin with one of each Markdown component, as new-plugin and add would
+    fn scaffold(dir: &Path, name: &str, desc: &str) -> Result<PathBuf> {
+        let slug = utils::slug(name)?;
+        let root = dir.join(&slug);
+        for sub in [".claude-plugin", "commands", "agents"] {
+            fs::create_dir_all(root.join(sub))?;
+        }
+        fs::create_dir_all(root.join("skills").join(&slug))?;
+
+        let templates = Templates::builtin();
+        let manifest = templates.plugin_manifest(&slug, desc)?;
+        fs::write(root.join(".claude-plugin/plugin.json"), manifest)?;
+        let skill = templates.skill(name, &slug, desc)?;
+        fs::write(root.join("skills").join(&slug).join("SKILL.md"), skill)?;
+        let command = templates.command(name, &slug, desc)?;
+        fs::write(root.join("commands").join(format!("{slug}.md")), command)?;
+        let agent = templates.agent(name, &slug, desc, "bash, search")?;
+        fs::write(root.join("agents").join(format!("{slug}.md")), agent)?;
+        Ok(root)
+    }
+
+    fn round_trips(name: &str, desc: &str) -> TestResult {
+        if utils::slug(name).is_err() || desc.trim().is_empty() {
+            return TestResult::discard();
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let root = scaffold(dir.path(), name, desc).unwrap();
+
+        let errors: Vec<_> = validate_plugin(&root, dir.path())
+            .unwrap()
+            .into_iter()
+            .filter(Diagnostic::is_error)
+            .collect();
+        if !errors.is_empty() {
+            return TestResult::error(format!("{errors:#?}"));
+        }
+
+        let manifest = PluginManifest::load(&root.join(".claude-plugin/plugin.json")).unwrap();
+        let slug = utils::slug(name).unwrap();
+        let command = fs::read_to_string(root.join("commands").join(format!("{slug}.md"))).unwrap();
+        let frontmatter = frontmatter::parse(&command).unwrap();
+        TestResult::from_bool(
+            manifest.meta.description.as_deref() == Some(desc)
+                && frontmatter.get("description").and_then(Value::as_str) == Some(desc),
+        )
+    }
+
+    #[test]
+    fn scaffolded_plugins_validate() {
+        fn property(name: Text, desc: Text) -> TestResult {
+            round_trips(&name.0, &desc.0)
+        }
+        QuickCheck::new()
+            .tests(300)
+            .quickcheck(property as fn(Text, Text) -> TestResult);
     }
-    let parts: Vec<&str> = content.splitn(3, "---").collect();
-    if parts.len() < 3 {
-        return false;
+
+    #[test]
+    fn descriptions_yaml_would_misread() {
+        for desc in [
+            "key: value",
+            "[not, a, list]",
+            "{not: a map}",
+            "say \"hi\" \\ bye",
+            "line one\nline two",
+            "# not a comment",
+            "- not an item",
+            "yes",
+            "null",
+            "1.0",
+            "  padded  ",
+            "---",
+            "*alias",
+            "&anchor",
+            "!tag",
+            "'quoted'",
+        ] {
+            let result = round_trips("My Tool", desc);
+            assert!(
+                !result.is_failure(),
+                "description {desc:?} did not round-trip"
+            );
+        }
+    }
+
+    #[test]
+    fn bad_path_exits_as_crashed() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("file.txt"), "").unwrap();
+        for path in [dir.path().join("missing"), dir.path().join("file.txt")] {
+            let code = run(Some(path.display().to_string()), false, Format::Json, false);
+            assert_eq!(code, ExitCode::from(EXIT_CRASHED), "{path:?}");
+        }
+
+        // An empty directory is an invalid plugin, not a crash
+        let code = run(
+            Some(dir.path().display().to_string()),
+            false,
+            Format::Json,
+            false,
+        );
+        assert_eq!(code, ExitCode::from(EXIT_INVALID));
+    }
+
+    #[test]
+    fn starter_kits_validate() {
+        let templates = Templates::builtin();
+        for (kit, _) in kits::builtin() {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().join(kit);
+            let vars = serde_json::json!({ "name": kit, "slug": kit, "description": "A kit" });
+            for file in kits::load(kit, &templates, &vars).unwrap() {
+                file.write(&root).unwrap();
+            }
+            let manifest = templates.plugin_manifest(kit, "A kit").unwrap();
+            fs::create_dir_all(root.join(".claude-plugin")).unwrap();
+            fs::write(root.join(".claude-plugin/plugin.json"), manifest).unwrap();
+
+            // Only the author (and minimal's README, which has no kit) is left to fill in
+            let unexpected: Vec<_> = validate_plugin(&root, dir.path())
+                .unwrap()
+                .into_iter()
+                .filter(|d| !matches!(d.rule, "placeholder-author" | "stub-readme"))
+                .collect();
+            assert!(unexpected.is_empty(), "kit {kit}: {unexpected:#?}");
+        }
     }
-    // Attempt to parse the YAML block
-    serde_yaml::from_str::<serde_json::Value>(parts[1]).is_ok()
 }
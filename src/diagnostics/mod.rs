@@ -0,0 +1,250 @@
+use colored::*;
+use serde::Serialize;
+use serde_json::json;
+
+/// Rule ids emitted by `validate`, with a one-line description for SARIF.
+pub const RULES: &[(&str, &str)] = &[
+    (
+        "missing-manifest",
+        "Plugin has no .claude-plugin/plugin.json",
+    ),
+    ("invalid-json", "Manifest is not valid JSON"),
+    (
+        "manifest-schema",
+        "Manifest field violates the plugin.json schema",
+    ),
+    (
+        "unknown-field",
+        "Manifest contains a field cforge does not recognise",
+    ),
+    (
+        "invalid-frontmatter",
+        "Component has missing or unparsable YAML frontmatter",
+    ),
+    (
+        "frontmatter-field",
+        "Component frontmatter is missing or has a malformed field",
+    ),
+    ("missing-skill-file", "Skill directory has no SKILL.md"),
+    (
+        "legacy-skill-layout",
+        "Skill uses the flat skills/<name>.md layout instead of skills/<name>/SKILL.md",
+    ),
+    ("hook-shebang", "Hook script has no shebang line"),
+    ("hook-executable", "Hook script is not executable"),
+    (
+        "hook-config",
+        "hooks.json is malformed or names an unknown event",
+    ),
+    (
+        "missing-hook-script",
+        "hooks.json runs a script that does not exist",
+    ),
+    (
+        "mcp-config",
+        ".mcp.json is malformed or a server is missing required fields",
+    ),
+    (
+        "mcp-relative-path",
+        "MCP server command or argument is not anchored to ${CLAUDE_PLUGIN_ROOT}",
+    ),
+    (
+        "invalid-marketplace",
+        "marketplace.json does not match the marketplace schema",
+    ),
+    (
+        "missing-plugin",
+        "Marketplace entry points at a directory that does not exist",
+    ),
+    (
+        "invalid-source",
+        "Marketplace entry has a malformed remote source",
+    ),
+    (
+        "marketplace-drift",
+        "Marketplace entry metadata differs from the plugin's plugin.json",
+    ),
+    (
+        "duplicate-plugin-name",
+        "Two marketplace entries share a plugin name",
+    ),
+    (
+        "duplicate-command-name",
+        "Two plugins define a slash command with the same name",
+    ),
+    ("empty-description", "Plugin has no description"),
+    (
+        "long-description",
+        "Description is longer than Claude's listings show",
+    ),
+    (
+        "placeholder-author",
+        "plugin.json still has the scaffolded \"Your Name\" author",
+    ),
+    ("stub-readme", "README.md is only the scaffolded title"),
+    (
+        "template-placeholder",
+        "Component still contains text from its scaffold template",
+    ),
+    (
+        "unreferenced-file",
+        "File is neither a component nor referenced by one",
+    ),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single finding, located by file and (when known) line/column or JSON pointer.
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub rule: &'static str,
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pointer: Option<String>,
+    pub message: String,
+    /// Code frame showing the offending source, for human-readable output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn error(rule: &'static str, file: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            rule,
+            file: file.into(),
+            line: None,
+            column: None,
+            pointer: None,
+            message: message.into(),
+            snippet: None,
+        }
+    }
+
+    pub fn warning(
+        rule: &'static str,
+        file: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(rule, file, message)
+        }
+    }
+
+    pub fn at(mut self, line: usize, column: usize) -> Self {
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
+
+    pub fn with_pointer(mut self, pointer: impl Into<String>) -> Self {
+        self.pointer = Some(pointer.into());
+        self
+    }
+
+    pub fn with_snippet(mut self, snippet: impl Into<String>) -> Self {
+        self.snippet = Some(snippet.into());
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// `file:line:col` (or `file#/pointer`) for human-readable output.
+    pub fn location(&self) -> String {
+        match (self.line, self.column, &self.pointer) {
+            (Some(l), Some(c), _) => format!("{}:{l}:{c}", self.file),
+            (Some(l), None, _) => format!("{}:{l}", self.file),
+            (None, _, Some(p)) => format!("{}#{p}", self.file),
+            _ => self.file.clone(),
+        }
+    }
+}
+
+pub fn print_text(diagnostics: &[Diagnostic]) {
+    for d in diagnostics {
+        let line = format!("{}: {} [{}]", d.location(), d.message, d.rule);
+        match d.severity {
+            Severity::Error => println!("{}", format!("❌ {line}").red()),
+            Severity::Warning => println!("{}", format!("⚠️  {line}").yellow()),
+        }
+        if let Some(snippet) = &d.snippet {
+            println!("{}", snippet.dimmed());
+        }
+    }
+}
+
+pub fn to_json(diagnostics: &[Diagnostic]) -> serde_json::Value {
+    let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+    json!({
+        "valid": errors == 0,
+        "errors": errors,
+        "warnings": diagnostics.len() - errors,
+        "diagnostics": diagnostics,
+    })
+}
+
+/// Renders diagnostics as a SARIF 2.1.0 log for code-scanning tools.
+pub fn to_sarif(diagnostics: &[Diagnostic]) -> serde_json::Value {
+    let rules: Vec<_> = RULES
+        .iter()
+        .map(|(id, text)| json!({ "id": id, "shortDescription": { "text": text } }))
+        .collect();
+
+    let results: Vec<_> = diagnostics
+        .iter()
+        .map(|d| {
+            let mut location = json!({
+                "physicalLocation": { "artifactLocation": { "uri": d.file } }
+            });
+            if let Some(line) = d.line {
+                let mut region = json!({ "startLine": line });
+                if let Some(column) = d.column {
+                    region["startColumn"] = json!(column);
+                }
+                location["physicalLocation"]["region"] = region;
+            }
+            let text = match &d.pointer {
+                Some(p) => format!("{p}: {}", d.message),
+                None => d.message.clone(),
+            };
+            json!({
+                "ruleId": d.rule,
+                "level": match d.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                },
+                "message": { "text": text },
+                "locations": [location],
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "cforge",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/bgreenwell/claude-forge",
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
@@ -0,0 +1,289 @@
+use serde_json::{Map, Value};
+
+/// Why a component's frontmatter could not be read, located in the source file.
+#[derive(Debug)]
+pub struct FrontmatterError {
+    pub message: String,
+    /// 1-based line in the original file.
+    pub line: usize,
+    /// 1-based column in the original file.
+    pub column: usize,
+}
+
+impl FrontmatterError {
+    fn new(message: impl Into<String>, line: usize, column: usize) -> Self {
+        Self {
+            message: message.into(),
+            line,
+            column,
+        }
+    }
+
+    /// Renders a rustc-style code frame pointing at the error:
+    ///
+    /// ```text
+    ///  --> skills/fetch.md:3:17
+    ///   |
+    /// 2 | description: Fetch logs
+    /// 3 | argument-hint: a: b
+    ///   |                 ^ mapping values are not allowed in this context
+    /// ```
+    pub fn code_frame(&self, file: &str, content: &str) -> String {
+        let lines: Vec<&str> = content.lines().collect();
+        let first = self.line.saturating_sub(1).max(1);
+        let last = self.line.min(lines.len().max(1));
+        let width = last.to_string().len();
+        let gutter = " ".repeat(width);
+
+        let mut out = format!(
+            "{gutter}--> {file}:{}:{}\n{gutter} |\n",
+            self.line, self.column
+        );
+        for n in first..=last {
+            let text = lines.get(n - 1).copied().unwrap_or("");
+            out.push_str(&format!("{n:>width$} | {text}\n"));
+        }
+        let pad = " ".repeat(self.column.saturating_sub(1));
+        out.push_str(&format!("{gutter} | {pad}^ {}", self.message));
+        out
+    }
+}
+
+/// Splits a component into its raw YAML block and body. The block must open
+/// on the first line and close with `---` on a line of its own; a leading BOM
+/// and CRLF line endings are accepted.
+pub fn split(content: &str) -> Result<(&str, &str), FrontmatterError> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+
+    // Byte offset of each line start, paired with the line sans terminator
+    let mut lines = content.split_inclusive('\n').scan(0, |offset, line| {
+        let start = *offset;
+        *offset += line.len();
+        Some((start, line.trim_end_matches(['\n', '\r'])))
+    });
+
+    if !lines.next().is_some_and(|(_, l)| is_delimiter(l)) {
+        return Err(FrontmatterError::new(
+            "missing frontmatter (first line must be ---)",
+            1,
+            1,
+        ));
+    }
+    let yaml_start = content.find('\n').map_or(content.len(), |i| i + 1);
+
+    let Some((yaml_end, _)) = lines.find(|(_, l)| is_delimiter(l)) else {
+        return Err(FrontmatterError::new(
+            "unterminated frontmatter (no closing --- line)",
+            1,
+            1,
+        ));
+    };
+    let yaml = &content[yaml_start..yaml_end];
+    let body = content[yaml_end..]
+        .split_once('\n')
+        .map_or("", |(_, rest)| rest);
+
+    Ok((yaml, body))
+}
+
+/// Parses the YAML frontmatter block at the top of a Markdown component.
+/// An empty block yields an empty mapping.
+pub fn parse(content: &str) -> Result<Map<String, Value>, FrontmatterError> {
+    let (yaml, _) = split(content)?;
+    match serde_yaml::from_str::<Value>(yaml) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(Value::Null) => Ok(Map::new()),
+        Ok(_) => Err(FrontmatterError::new(
+            "frontmatter must be a mapping of `key: value` pairs",
+            2,
+            1,
+        )),
+        Err(e) => {
+            // serde_yaml lines are 1-based within the block, which starts on line 2
+            let to_file = |line: usize, column: usize| (line + 1, column);
+            let (line, column) = match e.location() {
+                Some(loc) => to_file(loc.line(), loc.column()),
+                None => (2, 1),
+            };
+            let message = remap_locations(&e.to_string(), (line, column), to_file);
+            Err(FrontmatterError::new(message, line, column))
+        }
+    }
+}
+
+/// Rewrites the top-level `key: value` line of the frontmatter block, leaving
+/// the rest of the file untouched. Returns None if the block has no such key.
+pub fn replace_field(content: &str, key: &str, value: &str) -> Option<String> {
+    let (yaml, _) = split(content).ok()?;
+    let start = yaml.as_ptr() as usize - content.as_ptr() as usize;
+
+    let mut offset = start;
+    for line in yaml.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\n', '\r']);
+        if text
+            .strip_prefix(key)
+            .is_some_and(|rest| rest.starts_with(':'))
+        {
+            let end = offset + text.len();
+            return Some(format!(
+                "{}{key}: {value}{}",
+                &content[..offset],
+                &content[end..]
+            ));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+// A delimiter is exactly `---`, optionally followed by trailing whitespace
+fn is_delimiter(line: &str) -> bool {
+    line.trim_end() == "---"
+}
+
+// serde_yaml embeds "at line X column Y" marks relative to the block. Rewrite
+// them relative to the file, dropping the one already reported as the position
+fn remap_locations(
+    message: &str,
+    primary: (usize, usize),
+    to_file: impl Fn(usize, usize) -> (usize, usize),
+) -> String {
+    const MARK: &str = " at line ";
+    let number = |s: &str| {
+        let len = s.len() - s.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        s[..len].parse::<usize>().ok().map(|n| (n, len))
+    };
+
+    let mut out = String::new();
+    let mut rest = message;
+    while let Some(start) = rest.find(MARK) {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + MARK.len()..];
+        let parsed = number(after).and_then(|(line, len)| {
+            let after_col = after[len..].strip_prefix(" column ")?;
+            let (column, col_len) = number(after_col)?;
+            Some((line, column, &after_col[col_len..]))
+        });
+        match parsed {
+            Some((line, column, tail)) => {
+                let (line, column) = to_file(line, column);
+                if (line, column) != primary {
+                    out.push_str(&format!("{MARK}{line} column {column}"));
+                }
+                rest = tail;
+            }
+            None => {
+                out.push_str(MARK);
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_crlf_line_endings() {
+        let content = "---\r\ndescription: Fetch logs\r\n---\r\nBody\r\n";
+        let (_, body) = split(content).unwrap();
+        assert_eq!(body, "Body\r\n");
+        assert_eq!(parse(content).unwrap()["description"], "Fetch logs");
+    }
+
+    #[test]
+    fn accepts_leading_bom() {
+        let content = "\u{feff}---\ndescription: Fetch logs\n---\n";
+        assert_eq!(parse(content).unwrap()["description"], "Fetch logs");
+    }
+
+    #[test]
+    fn first_line_must_be_exactly_the_delimiter() {
+        for content in ["----\na: 1\n---\n", "--- a: 1\n---\n", "\n---\na: 1\n---\n"] {
+            let err = split(content).unwrap_err();
+            assert!(
+                err.message.starts_with("missing frontmatter"),
+                "{content:?}"
+            );
+            assert_eq!((err.line, err.column), (1, 1));
+        }
+        assert!(split("---  \na: 1\n---\t\n").is_ok());
+    }
+
+    #[test]
+    fn reports_unterminated_block() {
+        let err = split("---\na: 1\n----\n").unwrap_err();
+        assert!(err.message.starts_with("unterminated frontmatter"));
+    }
+
+    #[test]
+    fn dashes_inside_values_and_body_do_not_close_the_block() {
+        let content = "---\ndescription: a --- b\nnotes: |\n  ---\n---\nIntro\n---\nMore\n";
+        let (yaml, body) = split(content).unwrap();
+        assert_eq!(yaml, "description: a --- b\nnotes: |\n  ---\n");
+        assert_eq!(body, "Intro\n---\nMore\n");
+
+        let map = parse(content).unwrap();
+        assert_eq!(map["description"], "a --- b");
+        assert_eq!(map["notes"], "---\n");
+    }
+
+    #[test]
+    fn empty_block_is_an_empty_mapping() {
+        assert!(parse("---\n---\nBody\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_non_mapping_block() {
+        let err = parse("---\n- a\n- b\n---\n").unwrap_err();
+        assert!(err.message.contains("mapping"));
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn yaml_errors_point_into_the_file() {
+        let content = "---\ndescription: ok\nargument-hint: a: b\n---\n";
+        let err = parse(content).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 17));
+        // The position is reported once, not repeated relative to the block
+        assert_eq!(
+            err.message,
+            "mapping values are not allowed in this context"
+        );
+
+        let frame = err.code_frame("commands/fetch.md", content);
+        assert!(frame.starts_with(" --> commands/fetch.md:3:17\n"));
+        assert!(frame.contains("3 | argument-hint: a: b\n"));
+        assert!(
+            frame.ends_with("  |                 ^ mapping values are not allowed in this context")
+        );
+    }
+
+    #[test]
+    fn remaps_secondary_locations() {
+        let to_file = |line: usize, column: usize| (line + 1, column);
+        let message = "did not find expected key at line 2 column 1, \
+                       while parsing a block mapping at line 1 column 1";
+        assert_eq!(
+            remap_locations(message, (3, 1), to_file),
+            "did not find expected key, while parsing a block mapping at line 2 column 1"
+        );
+        assert_eq!(
+            remap_locations("x at line 5 without a column", (1, 1), to_file),
+            "x at line 5 without a column"
+        );
+    }
+
+    #[test]
+    fn replaces_only_the_frontmatter_field() {
+        let content = "---\r\nname: old\r\nnamespace: x\r\n---\r\nname: body\r\n";
+        assert_eq!(
+            replace_field(content, "name", "new").unwrap(),
+            "---\r\nname: new\r\nnamespace: x\r\n---\r\nname: body\r\n"
+        );
+        assert!(replace_field(content, "description", "d").is_none());
+    }
+}
@@ -0,0 +1,246 @@
+use crate::json;
+use crate::utils;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Placeholder Claude Code expands to the installed plugin's directory.
+pub const PLUGIN_ROOT_VAR: &str = "${CLAUDE_PLUGIN_ROOT}";
+
+/// Lifecycle events a plugin hook can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum HookEvent {
+    #[value(name = "PreToolUse", alias = "pre-tool-use")]
+    PreToolUse,
+    #[value(name = "PostToolUse", alias = "post-tool-use")]
+    PostToolUse,
+    #[value(
+        name = "UserPromptSubmit",
+        alias = "user-prompt-submit",
+        alias = "message"
+    )]
+    UserPromptSubmit,
+    #[value(name = "SessionStart", alias = "session-start", alias = "start")]
+    SessionStart,
+    #[value(name = "SessionEnd", alias = "session-end")]
+    SessionEnd,
+    #[value(name = "Stop", alias = "stop")]
+    Stop,
+    #[value(name = "SubagentStop", alias = "subagent-stop")]
+    SubagentStop,
+    #[value(name = "Notification", alias = "notification")]
+    Notification,
+    #[value(name = "PreCompact", alias = "pre-compact")]
+    PreCompact,
+}
+
+impl HookEvent {
+    /// Name used as the key in hooks.json.
+    pub fn name(self) -> &'static str {
+        match self {
+            HookEvent::PreToolUse => "PreToolUse",
+            HookEvent::PostToolUse => "PostToolUse",
+            HookEvent::UserPromptSubmit => "UserPromptSubmit",
+            HookEvent::SessionStart => "SessionStart",
+            HookEvent::SessionEnd => "SessionEnd",
+            HookEvent::Stop => "Stop",
+            HookEvent::SubagentStop => "SubagentStop",
+            HookEvent::Notification => "Notification",
+            HookEvent::PreCompact => "PreCompact",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::value_variants()
+            .iter()
+            .copied()
+            .find(|e| e.name() == name)
+    }
+
+    /// Default script file name, e.g. `pre-tool-use.sh`.
+    pub fn script_name(self) -> String {
+        let mut out = String::new();
+        for (i, c) in self.name().chars().enumerate() {
+            if c.is_ascii_uppercase() && i > 0 {
+                out.push('-');
+            }
+            out.push(c.to_ascii_lowercase());
+        }
+        out + ".sh"
+    }
+}
+
+/// Typed view of `hooks/hooks.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HooksConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub hooks: Map<String, Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// One `{ "matcher": ..., "hooks": [...] }` group under an event.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HookGroup {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matcher: Option<String>,
+    pub hooks: Vec<HookCommand>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HookCommand {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl HooksConfig {
+    pub fn path(plugin_root: &Path) -> PathBuf {
+        plugin_root.join("hooks/hooks.json")
+    }
+
+    /// Loads hooks.json, or an empty config if the plugin has none yet.
+    pub fn load_or_default(plugin_root: &Path) -> Result<Self> {
+        let path = Self::path(plugin_root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content =
+            fs::read_to_string(&path).with_context(|| format!("Failed to read {path:?}"))?;
+        serde_json::from_str(&content).with_context(|| format!("Invalid hooks.json {path:?}"))
+    }
+
+    pub fn save(&self, plugin_root: &Path) -> Result<()> {
+        json::write(&Self::path(plugin_root), self)
+    }
+
+    /// Groups registered for `event`.
+    pub fn groups(&self, event: &str) -> Result<Vec<HookGroup>> {
+        match self.hooks.get(event) {
+            Some(groups) => serde_json::from_value(groups.clone())
+                .with_context(|| format!("Malformed \"{event}\" entry in hooks.json")),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Adds `command` under `event`/`matcher`, reusing an existing group with
+    /// the same matcher. Returns false if the command was already wired up.
+    pub fn add_command(
+        &mut self,
+        event: HookEvent,
+        matcher: Option<String>,
+        command: String,
+        timeout: Option<u64>,
+    ) -> Result<bool> {
+        let mut groups = self.groups(event.name())?;
+        let hook = HookCommand {
+            kind: "command".to_string(),
+            command: Some(command),
+            timeout,
+            extra: Map::new(),
+        };
+
+        match groups.iter_mut().find(|g| g.matcher == matcher) {
+            Some(group) if group.hooks.iter().any(|h| h.command == hook.command) => {
+                return Ok(false)
+            }
+            Some(group) => group.hooks.push(hook),
+            None => groups.push(HookGroup {
+                matcher,
+                hooks: vec![hook],
+                extra: Map::new(),
+            }),
+        }
+
+        self.hooks
+            .insert(event.name().to_string(), serde_json::to_value(groups)?);
+        Ok(true)
+    }
+
+    /// Drops every hook whose command runs `script`, then any groups and
+    /// events left empty. Returns how many hooks were removed.
+    pub fn remove_script(&mut self, script: &Path, plugin_root: &Path) -> Result<usize> {
+        let mut removed = 0;
+        for event in self.hooks.keys().cloned().collect::<Vec<_>>() {
+            let mut groups = self.groups(&event)?;
+            let mut removed_here = 0;
+            for group in &mut groups {
+                let before = group.hooks.len();
+                group.hooks.retain(|h| {
+                    let command = h.command.as_deref().unwrap_or_default();
+                    !referenced_scripts(command, plugin_root)
+                        .iter()
+                        .any(|(path, _)| path == script)
+                });
+                removed_here += before - group.hooks.len();
+            }
+            if removed_here == 0 {
+                continue;
+            }
+            removed += removed_here;
+            groups.retain(|g| !g.hooks.is_empty());
+            if groups.is_empty() {
+                self.hooks.remove(&event);
+            } else {
+                self.hooks.insert(event, serde_json::to_value(groups)?);
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// Hook scripts in the plugin's hooks/ directory: those hooks.json runs
+/// directly, and those named after an event. READMEs and sourced helpers next
+/// to them are not hooks.
+pub fn scripts(plugin_root: &Path) -> Vec<PathBuf> {
+    // An unreadable hooks.json wires nothing; validate reports it separately
+    let config = HooksConfig::load_or_default(plugin_root).unwrap_or_default();
+    let wired: Vec<PathBuf> = config
+        .hooks
+        .keys()
+        .filter_map(|event| config.groups(event).ok())
+        .flatten()
+        .flat_map(|group| group.hooks)
+        .filter_map(|hook| hook.command)
+        .flat_map(|command| referenced_scripts(&command, plugin_root))
+        .filter(|(_, direct)| *direct)
+        .map(|(path, _)| path)
+        .collect();
+    utils::sorted_entries(&plugin_root.join("hooks"))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|path| {
+            let named_after_event = HookEvent::value_variants()
+                .iter()
+                .any(|e| path.file_name() == Some(e.script_name().as_ref()));
+            path.is_file() && (named_after_event || wired.contains(path))
+        })
+        .collect()
+}
+
+/// Script paths referenced through `${CLAUDE_PLUGIN_ROOT}` in a hook command,
+/// resolved against `plugin_root`. The flag is true for the token that is
+/// executed directly (the first one).
+pub fn referenced_scripts(command: &str, plugin_root: &Path) -> Vec<(PathBuf, bool)> {
+    command
+        .split_whitespace()
+        .enumerate()
+        .filter_map(|(i, token)| {
+            let token = token.trim_matches(|c| c == '"' || c == '\'');
+            let rel = token.strip_prefix(PLUGIN_ROOT_VAR)?;
+            Some((plugin_root.join(rel.trim_start_matches('/')), i == 0))
+        })
+        .collect()
+}
@@ -0,0 +1,446 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::io::Write;
+use std::ops::Range;
+use std::path::Path;
+
+/// Serializes `value` over the JSON file at `path`, preserving its formatting,
+/// and replaces the file atomically.
+pub fn write<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let original = match path.exists() {
+        true => fs::read_to_string(path).with_context(|| format!("Failed to read {path:?}"))?,
+        false => String::new(),
+    };
+    let value = serde_json::to_value(value)?;
+    write_atomic(path, &render(&original, &value))
+}
+
+/// `value` as JSON text laid out like `original` (which may be empty).
+pub fn render(original: &str, value: &Value) -> String {
+    let tree = serde_json::from_str::<Value>(original)
+        .ok()
+        .and_then(|_| Parser::new(original).value());
+    let style = Style::detect(original);
+
+    let mut out = String::new();
+    style.value(&mut out, original, value, tree.as_ref(), 0);
+    if style.trailing_newline {
+        out.push_str(style.newline);
+    }
+    out
+}
+
+/// Writes `contents` to a temporary file beside `path`, then renames it into
+/// place, so readers see either the old file or the new one, never a partial.
+pub fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let dir = path.parent().filter(|d| !d.as_os_str().is_empty());
+    let dir = dir.unwrap_or(Path::new("."));
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = dir.join(format!(".{name}.{}.tmp", std::process::id()));
+
+    let result = (|| -> Result<()> {
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp)?;
+        file.write_all(contents.as_bytes())?;
+        if let Ok(meta) = fs::metadata(path) {
+            file.set_permissions(meta.permissions())?;
+        }
+        file.sync_all()?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result.with_context(|| format!("Failed to write {path:?}"))
+}
+
+// Layout conventions taken from the existing file
+struct Style {
+    indent: String,
+    newline: &'static str,
+    trailing_newline: bool,
+}
+
+impl Style {
+    fn detect(text: &str) -> Self {
+        let indent = text
+            .lines()
+            .skip(1)
+            .map(|l| &l[..l.len() - l.trim_start().len()])
+            .find(|ws| !ws.is_empty())
+            .unwrap_or("  ");
+        Style {
+            indent: indent.to_string(),
+            newline: if text.contains("\r\n") { "\r\n" } else { "\n" },
+            trailing_newline: text.is_empty() || text.ends_with('\n'),
+        }
+    }
+
+    fn value(
+        &self,
+        out: &mut String,
+        text: &str,
+        value: &Value,
+        node: Option<&Node>,
+        depth: usize,
+    ) {
+        // Unchanged values keep their exact original spelling
+        if let Some(node) = node {
+            let original = &text[node.span.clone()];
+            if serde_json::from_str::<Value>(original).is_ok_and(|v| v == *value) {
+                out.push_str(original);
+                return;
+            }
+        }
+        // New containers are laid out over several lines; existing ones keep
+        // their single- or multi-line shape. An empty `[]` or `{}` has no shape
+        // to keep
+        let inline = node.is_some_and(|n| !n.is_empty() && !text[n.span.clone()].contains('\n'));
+
+        match value {
+            Value::Object(map) if !map.is_empty() => {
+                let members = match node.map(|n| &n.kind) {
+                    Some(Kind::Object(members)) => members.as_slice(),
+                    _ => &[],
+                };
+                // Keys already in the file first, in file order; new keys after
+                let mut keys: Vec<&String> = members
+                    .iter()
+                    .map(|(k, _)| k)
+                    .filter(|k| map.contains_key(*k))
+                    .collect();
+                let known = |k: &String| members.iter().any(|(m, _)| m == k);
+                keys.extend(map.keys().filter(|k| !known(k)));
+
+                self.open(out, '{', inline, depth);
+                for (i, key) in keys.into_iter().enumerate() {
+                    self.separator(out, i, inline, depth);
+                    out.push_str(&Value::String(key.clone()).to_string());
+                    out.push_str(": ");
+                    let child = members.iter().find(|(k, _)| k == key).map(|(_, n)| n);
+                    self.value(out, text, &map[key], child, depth + 1);
+                }
+                self.close(out, '}', inline, depth);
+            }
+            Value::Array(items) if !items.is_empty() => {
+                let children = match node.map(|n| &n.kind) {
+                    Some(Kind::Array(children)) => children.as_slice(),
+                    _ => &[],
+                };
+                let matched = match_items(text, items, children);
+                self.open(out, '[', inline, depth);
+                for (i, (item, child)) in items.iter().zip(matched).enumerate() {
+                    self.separator(out, i, inline, depth);
+                    self.value(out, text, item, child, depth + 1);
+                }
+                self.close(out, ']', inline, depth);
+            }
+            scalar => out.push_str(&scalar.to_string()),
+        }
+    }
+
+    fn open(&self, out: &mut String, bracket: char, inline: bool, depth: usize) {
+        out.push(bracket);
+        if !inline {
+            self.line(out, depth + 1);
+        }
+    }
+
+    fn separator(&self, out: &mut String, i: usize, inline: bool, depth: usize) {
+        match (i, inline) {
+            (0, _) => {}
+            (_, true) => out.push_str(", "),
+            (_, false) => {
+                out.push(',');
+                self.line(out, depth + 1);
+            }
+        }
+    }
+
+    fn close(&self, out: &mut String, bracket: char, inline: bool, depth: usize) {
+        if !inline {
+            self.line(out, depth);
+        }
+        out.push(bracket);
+    }
+
+    fn line(&self, out: &mut String, depth: usize) {
+        out.push_str(self.newline);
+        out.push_str(&self.indent.repeat(depth));
+    }
+}
+
+// The original node each array item was written from: an equal value
+// first, then an object with the same `name`, then the one at the same
+// position. Removing or reordering items then leaves the others as they were
+fn match_items<'n>(text: &str, items: &[Value], children: &'n [Node]) -> Vec<Option<&'n Node>> {
+    let originals: Vec<Option<Value>> = children
+        .iter()
+        .map(|n| serde_json::from_str(&text[n.span.clone()]).ok())
+        .collect();
+    let name = |v: &Value| v.get("name").filter(|n| n.is_string()).cloned();
+    let matches = |rule: usize, i: usize, item: &Value, j: usize| match rule {
+        0 => originals[j].as_ref() == Some(item),
+        1 => name(item).is_some() && originals[j].as_ref().and_then(name) == name(item),
+        _ => i == j,
+    };
+
+    let mut used = vec![false; children.len()];
+    let mut matched = vec![None; items.len()];
+    for rule in 0..3 {
+        for (i, item) in items.iter().enumerate() {
+            if matched[i].is_some() {
+                continue;
+            }
+            if let Some(j) = (0..children.len()).find(|&j| !used[j] && matches(rule, i, item, j)) {
+                used[j] = true;
+                matched[i] = Some(&children[j]);
+            }
+        }
+    }
+    matched
+}
+
+// Where each value sits in the original text
+struct Node {
+    span: Range<usize>,
+    kind: Kind,
+}
+
+impl Node {
+    fn is_empty(&self) -> bool {
+        match &self.kind {
+            Kind::Object(members) => members.is_empty(),
+            Kind::Array(children) => children.is_empty(),
+            Kind::Scalar => false,
+        }
+    }
+}
+
+enum Kind {
+    Object(Vec<(String, Node)>),
+    Array(Vec<Node>),
+    Scalar,
+}
+
+// Span-recording parser. Only run on text serde_json has already accepted,
+// so it does not re-validate
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Parser { text, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn value(&mut self) -> Option<Node> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let kind = match self.peek()? {
+            b'{' => {
+                self.pos += 1;
+                let mut members = Vec::new();
+                while self.next_item(b'}')? {
+                    self.skip_whitespace();
+                    let key_start = self.pos;
+                    self.string()?;
+                    let key = serde_json::from_str(&self.text[key_start..self.pos]).ok()?;
+                    self.skip_whitespace();
+                    self.pos += 1; // ':'
+                    members.push((key, self.value()?));
+                }
+                Kind::Object(members)
+            }
+            b'[' => {
+                self.pos += 1;
+                let mut children = Vec::new();
+                while self.next_item(b']')? {
+                    children.push(self.value()?);
+                }
+                Kind::Array(children)
+            }
+            b'"' => {
+                self.string()?;
+                Kind::Scalar
+            }
+            _ => {
+                while self
+                    .peek()
+                    .is_some_and(|b| !b.is_ascii_whitespace() && !b",]}".contains(&b))
+                {
+                    self.pos += 1;
+                }
+                Kind::Scalar
+            }
+        };
+        Some(Node {
+            span: start..self.pos,
+            kind,
+        })
+    }
+
+    // Consumes a separating ',' or the closing bracket; true while another
+    // item follows
+    fn next_item(&mut self, close: u8) -> Option<bool> {
+        self.skip_whitespace();
+        match self.peek()? {
+            b',' => {
+                self.pos += 1;
+                Some(true)
+            }
+            b if b == close => {
+                self.pos += 1;
+                Some(false)
+            }
+            _ => Some(true),
+        }
+    }
+
+    fn string(&mut self) -> Option<()> {
+        self.pos += 1; // opening quote
+        loop {
+            match self.peek()? {
+                b'\\' => self.pos += 2,
+                b'"' => {
+                    self.pos += 1;
+                    return Some(());
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn edit(original: &str, change: impl FnOnce(&mut Value)) -> String {
+        let mut value: Value = serde_json::from_str(original).unwrap();
+        change(&mut value);
+        render(original, &value)
+    }
+
+    #[test]
+    fn unchanged_files_round_trip_byte_for_byte() {
+        let original = "{\n    \"name\" : \"demo\",\n    \"keywords\": [ \"a\",\"b\" ],\n\n    \"nested\": {\"x\": 1.50, \"y\": \"\\u00e9\"}\n}\n";
+        assert_eq!(edit(original, |_| {}), original);
+    }
+
+    #[test]
+    fn first_item_in_an_empty_array_is_laid_out_on_its_own_lines() {
+        let original = "{\n  \"name\": \"market\",\n  \"plugins\": []\n}\n";
+        let output = edit(original, |v| {
+            v["plugins"] = json!([{ "name": "alpha", "source": "./plugins/alpha" }]);
+        });
+        assert_eq!(
+            output,
+            r#"{
+  "name": "market",
+  "plugins": [
+    {
+      "name": "alpha",
+      "source": "./plugins/alpha"
+    }
+  ]
+}
+"#
+        );
+    }
+
+    #[test]
+    fn removing_an_item_leaves_the_others_untouched() {
+        let original = r#"{
+  "plugins": [
+    { "name": "alpha", "source": "./a" },
+    {
+      "source": "./b",
+      "name": "beta"
+    },
+    { "name": "gamma", "source": "./c" }
+  ]
+}
+"#;
+        let output = edit(original, |v| {
+            v["plugins"].as_array_mut().unwrap().remove(1);
+        });
+        assert_eq!(
+            output,
+            r#"{
+  "plugins": [
+    { "name": "alpha", "source": "./a" },
+    { "name": "gamma", "source": "./c" }
+  ]
+}
+"#
+        );
+
+        // An edited item is matched by name, so it keeps its own layout
+        let output = edit(original, |v| {
+            let plugins = v["plugins"].as_array_mut().unwrap();
+            plugins.remove(0);
+            plugins[0]["source"] = json!("./beta");
+        });
+        assert_eq!(
+            output,
+            r#"{
+  "plugins": [
+    {
+      "source": "./beta",
+      "name": "beta"
+    },
+    { "name": "gamma", "source": "./c" }
+  ]
+}
+"#
+        );
+    }
+
+    #[test]
+    fn crlf_line_endings_are_kept() {
+        let original = "{\r\n  \"name\": \"demo\",\r\n  \"version\": \"1.0.0\"\r\n}\r\n";
+        let output = edit(original, |v| {
+            v["version"] = json!("1.1.0");
+            v["license"] = json!("MIT");
+        });
+        assert_eq!(
+            output,
+            "{\r\n  \"name\": \"demo\",\r\n  \"version\": \"1.1.0\",\r\n  \"license\": \"MIT\"\r\n}\r\n"
+        );
+    }
+
+    #[test]
+    fn tab_indentation_is_kept() {
+        let original = "{\n\t\"name\": \"demo\"\n}\n";
+        let output = edit(original, |v| v["author"] = json!({ "name": "Ada" }));
+        assert_eq!(
+            output,
+            "{\n\t\"name\": \"demo\",\n\t\"author\": {\n\t\t\"name\": \"Ada\"\n\t}\n}\n"
+        );
+    }
+
+    #[test]
+    fn a_missing_trailing_newline_stays_missing() {
+        let original = "{\n  \"version\": \"1.0.0\"\n}";
+        let output = edit(original, |v| v["version"] = json!("2.0.0"));
+        assert_eq!(output, "{\n  \"version\": \"2.0.0\"\n}");
+    }
+}
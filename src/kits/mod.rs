@@ -0,0 +1,459 @@
+use crate::templates::Templates;
+use anyhow::{anyhow, Context, Result};
+use colored::*;
+use serde_json::Value;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use walkdir::WalkDir;
+
+/// A file a starter kit adds to a new plugin, relative to the plugin root.
+pub struct KitFile {
+    pub path: PathBuf,
+    pub contents: Vec<u8>,
+    pub executable: bool,
+}
+
+impl KitFile {
+    pub fn write(&self, root: &Path) -> Result<()> {
+        let path = root.join(&self.path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, &self.contents).with_context(|| format!("Failed to write {path:?}"))?;
+        #[cfg(unix)]
+        if self.executable {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+        }
+        println!("{} {:?}", "Created file:".green(), path);
+        Ok(())
+    }
+}
+
+// A working example a kit can include: its files and the README section
+// describing them
+struct Part {
+    readme: &'static str,
+    files: &'static [(&'static str, &'static str)],
+}
+
+/// Built-in kits: name, summary and the parts each one includes.
+const BUILTIN: &[(&str, &str, &[Part])] = &[
+    ("minimal", "plugin.json and README.md only", &[]),
+    ("commands-only", "an example slash command", &[COMMANDS]),
+    (
+        "hook-guard",
+        "a PreToolUse hook that blocks destructive Bash commands",
+        &[HOOK_GUARD],
+    ),
+    (
+        "mcp-wrapper",
+        "a dependency-free stdio MCP server stub wired up in .mcp.json",
+        &[MCP_WRAPPER],
+    ),
+    (
+        "full",
+        "a command, skill, agent, guard hook and MCP server",
+        &[COMMANDS, SKILL, AGENT, HOOK_GUARD, MCP_WRAPPER],
+    ),
+];
+
+/// Built-in kit names and summaries, for help and listings.
+pub fn builtin() -> impl Iterator<Item = (&'static str, &'static str)> {
+    BUILTIN.iter().map(|(name, summary, _)| (*name, *summary))
+}
+
+const README_HEADER: &str = "# {{ name }}
+
+{{ description }}
+";
+
+const COMMANDS: Part = Part {
+    readme: "
+## Commands
+
+- `/{{ name }}:hello [name]` (`commands/hello.md`) greets you and summarizes the project in the current directory.
+",
+    files: &[(
+        "commands/hello.md",
+        r#"---
+description: Greet the user and summarize the current project
+argument-hint: [name]
+---
+
+Greet $ARGUMENTS (or the user, if no name was given), then summarize the
+repository in the current working directory in three bullets: what it is,
+how to build it, and how to run its tests.
+"#,
+    )],
+};
+
+const SKILL: Part = Part {
+    readme: "
+## Skills
+
+- `summarize-changes` (`skills/summarize-changes/SKILL.md`) turns uncommitted git changes into a commit message.
+",
+    files: &[(
+        "skills/summarize-changes/SKILL.md",
+        r#"---
+name: summarize-changes
+description: Summarize uncommitted changes in the current git repository. Use when the user asks what changed or wants a commit message.
+---
+
+# Summarize changes
+
+1. Run `git status --short` and `git diff --stat` to see which files changed.
+2. Read the diffs of the most significant files with `git diff -- <file>`.
+3. Reply with a one-line summary suitable as a commit subject, followed by a
+   short bullet list of the notable changes.
+"#,
+    )],
+};
+
+const AGENT: Part = Part {
+    readme: "
+## Agents
+
+- `reviewer` (`agents/reviewer.md`) reviews a diff for bugs, missing tests and unclear naming.
+",
+    files: &[(
+        "agents/reviewer.md",
+        r#"---
+description: Reviews a diff for bugs, missing tests and unclear naming
+capabilities: [code-review, testing]
+---
+
+# Reviewer Agent
+
+Review the changes you are given as a careful senior engineer would. For each
+file, point out likely bugs first, then missing or weak tests, then naming and
+readability problems. Quote the relevant lines, explain why each point
+matters, and suggest a concrete fix. Finish with a verdict: approve, or the
+changes needed before merging.
+"#,
+    )],
+};
+
+const HOOK_GUARD: Part = Part {
+    readme: r#"
+## Hooks
+
+`hooks/guard.sh` runs before every Bash tool call (wired up in `hooks/hooks.json`) and blocks destructive commands such as `rm -rf /`, `git push --force` and `git reset --hard`. Edit its `blocked` list to suit your project, then try it:
+
+    cforge hook test PreToolUse --input '{"command": "git reset --hard"}'
+"#,
+    files: &[
+        (
+            "hooks/hooks.json",
+            r#"{
+  "hooks": {
+    "PreToolUse": [
+      {
+        "matcher": "Bash",
+        "hooks": [
+          {
+            "type": "command",
+            "command": "${CLAUDE_PLUGIN_ROOT}/hooks/guard.sh"
+          }
+        ]
+      }
+    ]
+  }
+}
+"#,
+        ),
+        (
+            "hooks/guard.sh",
+            r#"#!/bin/bash
+# PreToolUse guard: blocks Bash commands that are destructive or hard to undo.
+# Claude Code passes the tool call as JSON on stdin; exit 2 blocks it and
+# shows stderr to Claude.
+input=$(cat)
+
+# The command string from {"tool_input": {"command": "..."}}
+command=$(printf '%s' "$input" | sed -n 's/.*"command"[[:space:]]*:[[:space:]]*"\(.*\)".*/\1/p' | head -n 1)
+
+blocked=(
+  'rm -rf /'
+  'rm -rf ~'
+  'git push --force'
+  'git push -f'
+  'git reset --hard'
+  'mkfs'
+  'dd if='
+)
+for pattern in "${blocked[@]}"; do
+  if [[ "$command" == *"$pattern"* ]]; then
+    echo "Blocked by {{ name }}: the command contains \"$pattern\". Ask the user to run it themselves if it is intended." >&2
+    exit 2
+  fi
+done
+exit 0
+"#,
+        ),
+    ],
+};
+
+const MCP_WRAPPER: Part = Part {
+    readme: r#"
+## MCP server
+
+`.mcp.json` starts `server/server.py`, a dependency-free MCP server speaking JSON-RPC over stdio with a single `echo` tool. Replace `TOOLS` and `call_tool` with the API you are wrapping, and check it by hand:
+
+    printf '%s\n' '{"jsonrpc": "2.0", "id": 1, "method": "tools/list"}' | python3 server/server.py
+"#,
+    files: &[
+        (
+            ".mcp.json",
+            r#"{
+  "mcpServers": {
+    {{ name | json }}: {
+      "command": "python3",
+      "args": ["${CLAUDE_PLUGIN_ROOT}/server/server.py"]
+    }
+  }
+}
+"#,
+        ),
+        (
+            "server/server.py",
+            r#"#!/usr/bin/env python3
+"""MCP server stub for {{ name }}: one `echo` tool over stdio.
+
+Replace TOOLS and call_tool with the API this plugin wraps. Messages are
+newline-delimited JSON-RPC 2.0, as the MCP stdio transport specifies.
+"""
+import json
+import sys
+
+TOOLS = [
+    {
+        "name": "echo",
+        "description": "Return the given text unchanged.",
+        "inputSchema": {
+            "type": "object",
+            "properties": {"text": {"type": "string"}},
+            "required": ["text"],
+        },
+    }
+]
+
+
+def call_tool(name, arguments):
+    if name == "echo":
+        return arguments.get("text", "")
+    raise ValueError(f"unknown tool: {name}")
+
+
+def handle(request):
+    method = request.get("method")
+    params = request.get("params") or {}
+    if method == "initialize":
+        return {
+            "protocolVersion": params.get("protocolVersion", "2025-06-18"),
+            "capabilities": {"tools": {}},
+            "serverInfo": {"name": "{{ name }}", "version": "0.1.0"},
+        }
+    if method == "tools/list":
+        return {"tools": TOOLS}
+    if method == "tools/call":
+        text = call_tool(params["name"], params.get("arguments") or {})
+        return {"content": [{"type": "text", "text": text}]}
+    if method == "ping":
+        return {}
+    raise LookupError(method)
+
+
+def main():
+    for line in sys.stdin:
+        if not line.strip():
+            continue
+        request = json.loads(line)
+        if "id" not in request:
+            continue  # notifications get no reply
+        response = {"jsonrpc": "2.0", "id": request["id"]}
+        try:
+            response["result"] = handle(request)
+        except LookupError as e:
+            response["error"] = {"code": -32601, "message": f"method not found: {e}"}
+        except Exception as e:
+            response["error"] = {"code": -32603, "message": str(e)}
+        print(json.dumps(response), flush=True)
+
+
+if __name__ == "__main__":
+    main()
+"#,
+        ),
+    ],
+};
+
+/// Suffix marking a file in a custom kit as a template.
+const TEMPLATE_SUFFIX: &str = ".j2";
+
+/// Renders the kit named by `spec`: a built-in kit, a local directory, or a
+/// git URL to clone. Files keep their paths; both paths and contents are
+/// templates rendered with `vars`.
+pub fn load(spec: &str, templates: &Templates, vars: &Value) -> Result<Vec<KitFile>> {
+    if let Some((_, _, parts)) = BUILTIN.iter().find(|(name, _, _)| *name == spec) {
+        return load_builtin(parts, templates, vars);
+    }
+    let dir = Path::new(spec);
+    if dir.is_dir() {
+        return load_dir(dir, templates, vars);
+    }
+    if spec.contains("://") || spec.starts_with("git@") {
+        let clone = std::env::temp_dir().join(format!("cforge-kit-{}", std::process::id()));
+        let status = Command::new("git")
+            .args(["clone", "--quiet", "--depth", "1", spec])
+            .arg(&clone)
+            .status()
+            .context("Failed to run git")?;
+        let files = match status.success() {
+            true => load_dir(&clone, templates, vars),
+            false => Err(anyhow!("git clone {spec} failed")),
+        };
+        let _ = fs::remove_dir_all(&clone);
+        return files;
+    }
+    let known: Vec<_> = builtin().map(|(name, _)| name).collect();
+    Err(anyhow!(
+        "Unknown starter kit '{spec}': expected one of {}, a kit directory or a git URL",
+        known.join(", ")
+    ))
+}
+
+fn load_builtin(parts: &[Part], templates: &Templates, vars: &Value) -> Result<Vec<KitFile>> {
+    let mut files = Vec::new();
+    for (path, source) in parts.iter().flat_map(|p| p.files) {
+        files.push(KitFile {
+            path: PathBuf::from(path),
+            contents: templates
+                .render_str(&format!("built-in {path}"), source, vars.clone())?
+                .into_bytes(),
+            executable: path.ends_with(".sh") || path.ends_with(".py"),
+        });
+    }
+    // Kits with examples document them; `minimal` keeps the README template
+    if !parts.is_empty() {
+        let readme: String = std::iter::once(README_HEADER)
+            .chain(parts.iter().map(|p| p.readme))
+            .collect();
+        files.push(KitFile {
+            path: PathBuf::from("README.md"),
+            contents: templates
+                .render_str("built-in README.md", &readme, vars.clone())?
+                .into_bytes(),
+            executable: false,
+        });
+    }
+    Ok(files)
+}
+
+// Every file under `dir` except git metadata. Files ending in `.j2` are
+// templates: their path and text are rendered and the suffix dropped.
+// Everything else is copied byte for byte, so workflows using `${{ }}` or
+// scripts using `${#array[@]}` survive
+fn load_dir(dir: &Path, templates: &Templates, vars: &Value) -> Result<Vec<KitFile>> {
+    let mut files = Vec::new();
+    let entries = WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| e.file_name() != ".git");
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+        let rel = rel.to_string_lossy();
+        let bytes = fs::read(entry.path())?;
+        let (path, contents) = match rel.strip_suffix(TEMPLATE_SUFFIX) {
+            Some(stem) => {
+                let shown = entry.path().display().to_string();
+                let text = String::from_utf8(bytes)
+                    .map_err(|_| anyhow!("{shown} is a template but not UTF-8 text"))?;
+                let label = format!("{shown} (file name)");
+                let path = templates.render_str(&label, stem, vars.clone())?;
+                if !is_confined(Path::new(&path)) {
+                    return Err(anyhow!(
+                        "{shown} renders to {path:?}, which is not a path inside the plugin"
+                    ));
+                }
+                // Ends in the output name, so a .json template is checked as JSON
+                let label = format!("{shown} as {path}");
+                let text = templates.render_str(&label, &text, vars.clone())?;
+                (path, text.into_bytes())
+            }
+            None => (rel.into_owned(), bytes),
+        };
+        #[cfg(unix)]
+        let executable = {
+            use std::os::unix::fs::PermissionsExt;
+            entry.metadata()?.permissions().mode() & 0o111 != 0
+        };
+        #[cfg(not(unix))]
+        let executable = false;
+
+        files.push(KitFile {
+            path: PathBuf::from(path),
+            contents,
+            executable,
+        });
+    }
+    Ok(files)
+}
+
+// A rendered path stays inside the plugin only if every part is a plain name
+fn is_confined(path: &Path) -> bool {
+    path.components().next().is_some()
+        && path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn load_kit(files: &[(&str, &str)]) -> Result<Vec<KitFile>> {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, text) in files {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, text).unwrap();
+        }
+        let vars = json!({ "name": "safe-shell", "slug": "safe-shell", "description": "d" });
+        load(dir.path().to_str().unwrap(), &Templates::builtin(), &vars)
+    }
+
+    #[test]
+    fn renders_only_j2_files() {
+        let files = load_kit(&[
+            ("commands/{{ slug }}.md.j2", "# {{ name }}\n"),
+            ("scripts/run.sh", "echo ${#items[@]} {{ name }}\n"),
+        ])
+        .unwrap();
+        let paths: Vec<_> = files.iter().map(|f| f.path.to_str().unwrap()).collect();
+        assert_eq!(paths, ["commands/safe-shell.md", "scripts/run.sh"]);
+        assert_eq!(files[0].contents, b"# safe-shell\n");
+        assert_eq!(files[1].contents, b"echo ${#items[@]} {{ name }}\n");
+    }
+
+    #[test]
+    fn rejects_rendered_paths_outside_the_plugin() {
+        for name in [
+            r#"{{ "..|..|escaped" | replace("|", "\u002f") }}.txt.j2"#,
+            r#"{{ "|tmp|escaped" | replace("|", "\u002f") }}.txt.j2"#,
+            r#"{{ ".|b" | replace("|", "\u002f") }}.txt.j2"#,
+            "{{ '' }}.j2",
+        ] {
+            let err = load_kit(&[(name, "x")]).err().expect(name);
+            assert!(
+                err.to_string().contains("not a path inside the plugin"),
+                "{err}"
+            );
+        }
+    }
+}
@@ -0,0 +1,481 @@
+use crate::diagnostics::{Diagnostic, Severity, RULES};
+use crate::frontmatter;
+use crate::hooks::HookEvent;
+use crate::manifest::Marketplace;
+use crate::templates::{self, Templates};
+use crate::utils::{relative, sorted_entries};
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Descriptions longer than this are truncated in Claude's listings.
+pub const MAX_DESCRIPTION: usize = 1024;
+
+/// Author fields `new-plugin` writes until someone fills them in.
+const PLACEHOLDER_AUTHORS: &[&str] = &[
+    templates::AUTHOR_NAME_PLACEHOLDER,
+    templates::AUTHOR_EMAIL_PLACEHOLDER,
+];
+
+/// Component text the templates leave for authors to replace.
+const PLACEHOLDER_TEXT: &[&str] = &[
+    templates::INSTRUCTIONS_PLACEHOLDER,
+    templates::AGENT_PLACEHOLDER,
+    templates::ARGUMENT_HINT_PLACEHOLDER,
+];
+
+/// Rules `validate --strict` turns into errors: scaffolding that must not ship.
+const STRICT_RULES: &[&str] = &["placeholder-author", "template-placeholder"];
+
+/// Severity a team assigns to a rule in `.cforge.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Off,
+    #[serde(alias = "warning")]
+    Warn,
+    Error,
+}
+
+/// Per-rule overrides read from `.cforge.toml`:
+///
+/// ```toml
+/// [rules]
+/// duplicate-command-name = "error"
+/// unreferenced-file = "off"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+    #[serde(default)]
+    rules: BTreeMap<String, Level>,
+    /// Set by `validate --strict`, never read from the file.
+    #[serde(skip)]
+    pub strict: bool,
+}
+
+impl LintConfig {
+    pub const FILE: &'static str = ".cforge.toml";
+
+    /// Loads the nearest `.cforge.toml` at or above `start`, so plugins
+    /// validated on their own still pick up their marketplace's settings.
+    pub fn discover(start: &Path) -> Result<Self> {
+        let start = start.canonicalize().unwrap_or_else(|_| start.to_path_buf());
+        match start
+            .ancestors()
+            .map(|d| d.join(Self::FILE))
+            .find(|p| p.is_file())
+        {
+            Some(path) => Self::load(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    fn load(path: &Path) -> Result<Self> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Failed to read {path:?}"))?;
+        let config: Self =
+            toml::from_str(&content).with_context(|| format!("Invalid config {path:?}"))?;
+        for rule in config.rules.keys() {
+            if !RULES.iter().any(|(id, _)| id == rule) {
+                let known: Vec<_> = RULES.iter().map(|(id, _)| *id).collect();
+                return Err(anyhow!(
+                    "Unknown rule \"{rule}\" in {path:?}. Known rules: {}",
+                    known.join(", ")
+                ));
+            }
+        }
+        Ok(config)
+    }
+
+    /// Re-levels diagnostics per the configured rules, dropping those set to
+    /// off. In strict mode leftover placeholders are errors unless turned off.
+    pub fn apply(&self, diagnostics: &mut Vec<Diagnostic>) {
+        diagnostics.retain_mut(|d| {
+            match self.rules.get(d.rule) {
+                None => {}
+                Some(Level::Off) => return false,
+                Some(Level::Warn) => d.severity = Severity::Warning,
+                Some(Level::Error) => d.severity = Severity::Error,
+            }
+            if self.strict && STRICT_RULES.contains(&d.rule) {
+                d.severity = Severity::Error;
+            }
+            true
+        });
+    }
+}
+
+/// Quality rules for a single plugin; file names are relative to `base`.
+pub fn check_plugin(root: &Path, base: &Path) -> Result<Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+    let rel = |p: &Path| relative(p, base);
+
+    let manifest_path = root.join(".claude-plugin/plugin.json");
+    let manifest = fs::read_to_string(&manifest_path)
+        .ok()
+        .and_then(|c| serde_json::from_str::<Value>(&c).ok());
+    if let Some(manifest) = &manifest {
+        let file = rel(&manifest_path);
+        match manifest.get("description").and_then(Value::as_str) {
+            Some(d) if !d.trim().is_empty() => {
+                check_length(d, &file, Some("/description"), &mut diagnostics)
+            }
+            _ => diagnostics.push(
+                Diagnostic::warning(
+                    "empty-description",
+                    file.clone(),
+                    "plugin has no description",
+                )
+                .with_pointer("/description"),
+            ),
+        }
+
+        let author_fields: Vec<(String, &str)> = match manifest.get("author") {
+            Some(Value::String(name)) => vec![("/author".to_string(), name)],
+            Some(Value::Object(author)) => ["name", "email"]
+                .iter()
+                .filter_map(|k| Some((format!("/author/{k}"), author.get(*k)?.as_str()?)))
+                .collect(),
+            _ => Vec::new(),
+        };
+        for (ptr, value) in author_fields {
+            if PLACEHOLDER_AUTHORS.contains(&value.trim()) {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "placeholder-author",
+                        file.clone(),
+                        format!("author still has the template placeholder \"{value}\""),
+                    )
+                    .with_pointer(ptr),
+                );
+            }
+        }
+    }
+
+    // Component descriptions: presence is checked by validate, length here
+    for path in component_files(root)? {
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if let Ok(frontmatter) = frontmatter::parse(&content) {
+            if let Some(d) = frontmatter.get("description").and_then(Value::as_str) {
+                check_length(d, &rel(&path), None, &mut diagnostics);
+            }
+        }
+        check_placeholders(&content, &rel(&path), &mut diagnostics);
+    }
+
+    let (builtin, project) = (Templates::builtin(), Templates::discover(root));
+    for path in sorted_entries(&root.join("hooks"))? {
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        // Either the built-in script or the project's own hook.sh template
+        let scaffolded = HookEvent::value_variants().iter().any(|e| {
+            [&builtin, &project]
+                .iter()
+                .any(|t| t.hook_script(e.name()).is_ok_and(|s| s == content))
+        });
+        if scaffolded {
+            diagnostics.push(Diagnostic::warning(
+                "template-placeholder",
+                rel(&path),
+                "hook script is still the scaffolded no-op",
+            ));
+        }
+    }
+
+    let readme = root.join("README.md");
+    if let Ok(content) = fs::read_to_string(&readme) {
+        let mut lines = content.lines().map(str::trim).filter(|l| !l.is_empty());
+        let heading_only =
+            lines.next().is_none_or(|l| l.starts_with('#')) && lines.next().is_none();
+        if heading_only {
+            diagnostics.push(Diagnostic::warning(
+                "stub-readme",
+                rel(&readme),
+                "README.md has no content beyond its title",
+            ));
+        }
+    }
+
+    for path in unreferenced_files(root)? {
+        diagnostics.push(Diagnostic::warning(
+            "unreferenced-file",
+            rel(&path),
+            "file is not a component and nothing in the plugin refers to it",
+        ));
+    }
+
+    Ok(diagnostics)
+}
+
+/// Rules that need the whole marketplace: name collisions between plugins.
+pub fn check_marketplace(root: &Path, marketplace: &Marketplace) -> Result<Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+    let marketplace_file = relative(&root.join(".claude-plugin/marketplace.json"), root);
+
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    for (idx, plugin) in marketplace.plugins.iter().enumerate() {
+        if plugin
+            .meta
+            .description
+            .as_deref()
+            .is_some_and(|d| d.trim().is_empty())
+        {
+            diagnostics.push(
+                Diagnostic::warning(
+                    "empty-description",
+                    marketplace_file.clone(),
+                    format!("plugin '{}' has an empty description", plugin.name),
+                )
+                .with_pointer(format!("/plugins/{idx}/description")),
+            );
+        }
+        match seen.get(plugin.name.as_str()) {
+            Some(first) => diagnostics.push(
+                Diagnostic::error(
+                    "duplicate-plugin-name",
+                    marketplace_file.clone(),
+                    format!(
+                        "plugin name '{}' is already used by /plugins/{first}",
+                        plugin.name
+                    ),
+                )
+                .with_pointer(format!("/plugins/{idx}/name")),
+            ),
+            None => {
+                seen.insert(&plugin.name, idx);
+            }
+        }
+    }
+
+    // Slash commands from different plugins share one namespace in the menu
+    let mut commands: BTreeMap<String, (String, PathBuf)> = BTreeMap::new();
+    for plugin in &marketplace.plugins {
+        let Some(plugin_root) = plugin.source.local_path(root).filter(|p| p.is_dir()) else {
+            continue;
+        };
+        for path in sorted_entries(&plugin_root.join("commands"))? {
+            if path.extension().is_none_or(|ext| ext != "md") {
+                continue;
+            }
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            match commands.get(&name) {
+                Some((owner, _)) if *owner == plugin.name => {}
+                Some((owner, first)) => diagnostics.push(Diagnostic::warning(
+                    "duplicate-command-name",
+                    relative(&path, root),
+                    format!(
+                        "/{name} is also defined by plugin '{owner}' ({})",
+                        relative(first, root)
+                    ),
+                )),
+                None => {
+                    commands.insert(name, (plugin.name.clone(), path));
+                }
+            }
+        }
+    }
+
+    Ok(diagnostics)
+}
+
+// Flags each line still carrying template text, e.g. "(Instructions for Claude: ..."
+fn check_placeholders(content: &str, file: &str, out: &mut Vec<Diagnostic>) {
+    for (i, line) in content.lines().enumerate() {
+        for placeholder in PLACEHOLDER_TEXT {
+            if let Some(col) = line.find(placeholder) {
+                out.push(
+                    Diagnostic::warning(
+                        "template-placeholder",
+                        file,
+                        format!("scaffold placeholder \"{placeholder}\" was never filled in"),
+                    )
+                    .at(i + 1, line[..col].chars().count() + 1),
+                );
+            }
+        }
+    }
+}
+
+fn check_length(description: &str, file: &str, ptr: Option<&str>, out: &mut Vec<Diagnostic>) {
+    let len = description.chars().count();
+    if len > MAX_DESCRIPTION {
+        let d = Diagnostic::warning(
+            "long-description",
+            file,
+            format!("description is {len} characters (max {MAX_DESCRIPTION})"),
+        );
+        out.push(match ptr {
+            Some(ptr) => d.with_pointer(ptr),
+            None => d,
+        });
+    }
+}
+
+// Markdown components in either skill layout, plus commands and agents
+fn component_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in sorted_entries(&root.join("skills"))? {
+        if path.is_dir() {
+            files.push(path.join("SKILL.md"));
+        } else if path.extension().is_some_and(|ext| ext == "md") {
+            files.push(path);
+        }
+    }
+    for dir in ["commands", "agents"] {
+        for path in sorted_entries(&root.join(dir))? {
+            if path.extension().is_some_and(|ext| ext == "md") {
+                files.push(path);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Directories holding dependencies or build output rather than plugin files.
+const VENDORED_DIRS: &[&str] = &["node_modules", "target", "__pycache__", "venv"];
+
+/// Files larger than this are not read for references.
+const MAX_TEXT_BYTES: u64 = 1024 * 1024;
+
+// Files Claude Code never loads on its own and no other file mentions by
+// name. Components, well-known docs and hidden files are always kept, and
+// vendored or git-ignored directories are skipped entirely
+fn unreferenced_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let components = component_files(root)?;
+    let ignored = gitignore_patterns(root);
+    let all: Vec<PathBuf> = WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            let rel = e.path().strip_prefix(root).unwrap_or(e.path());
+            name != ".git"
+                && !(e.file_type().is_dir() && VENDORED_DIRS.contains(&name.as_ref()))
+                && !ignored
+                    .iter()
+                    .any(|p| p.matches_path(rel) || p.matches(&name))
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .collect();
+
+    // Every name-like word in every text file, hidden ones included (.mcp.json
+    // and plugin.json references count), with the file that mentions it
+    let mut mentions: HashMap<String, Mention> = HashMap::new();
+    for (i, path) in all.iter().enumerate() {
+        let Some(text) = read_text(path) else {
+            continue;
+        };
+        for word in text.split(|c: char| !(c.is_alphanumeric() || "._-".contains(c))) {
+            for word in [word, word.trim_end_matches(['.', '-'])] {
+                if word.is_empty() {
+                    continue;
+                }
+                mentions
+                    .entry(word.to_string())
+                    .and_modify(|m| {
+                        if *m != Mention::By(i) {
+                            *m = Mention::Many;
+                        }
+                    })
+                    .or_insert(Mention::By(i));
+            }
+        }
+    }
+
+    Ok(all
+        .iter()
+        .enumerate()
+        .filter(|(i, path)| {
+            let rel = path.strip_prefix(root).unwrap_or(path);
+            let hidden = rel
+                .components()
+                .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+            let name = path.file_name().unwrap().to_string_lossy();
+            let top_level_doc = rel.components().count() == 1
+                && ["README", "LICENSE", "CHANGELOG", "CLAUDE"]
+                    .iter()
+                    .any(|doc| name.to_uppercase().starts_with(doc));
+            let known = hidden
+                || top_level_doc
+                || components.contains(path)
+                || rel == Path::new("hooks/hooks.json");
+            let mentioned = match mentions.get(name.as_ref()) {
+                Some(Mention::By(by)) => by != i,
+                Some(Mention::Many) => true,
+                None => false,
+            };
+            !known && !mentioned
+        })
+        .map(|(_, p)| p.to_path_buf())
+        .collect())
+}
+
+// Which files mention a word: only the one at this index, or several
+#[derive(PartialEq)]
+enum Mention {
+    By(usize),
+    Many,
+}
+
+// Contents of a reasonably small UTF-8 file; binaries and large files are skipped
+fn read_text(path: &Path) -> Option<String> {
+    if fs::metadata(path).ok()?.len() > MAX_TEXT_BYTES {
+        return None;
+    }
+    let text = String::from_utf8(fs::read(path).ok()?).ok()?;
+    (!text.contains('\0')).then_some(text)
+}
+
+// Patterns from the plugin's .gitignore, matched against a relative path or a
+// bare file name. Negations are not supported, so `!keep` lines are dropped
+fn gitignore_patterns(root: &Path) -> Vec<glob::Pattern> {
+    let Ok(text) = fs::read_to_string(root.join(".gitignore")) else {
+        return Vec::new();
+    };
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with(['#', '!']))
+        .filter_map(|line| glob::Pattern::new(line.trim_matches('/')).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unreferenced_files_skips_vendored_and_ignored_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for (path, text) in [
+            ("README.md", "Run `scripts/run.sh`."),
+            ("scripts/run.sh", "source ./lib.sh"),
+            ("scripts/lib.sh", "echo lib"),
+            ("scripts/orphan.sh", "echo orphan.sh"),
+            ("server/node_modules/x/index.js", "module"),
+            ("target/debug/out", "built"),
+            ("dist/bundle.js", "bundle"),
+            (".gitignore", "# build output\n/dist/\n"),
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, text).unwrap();
+        }
+        // Mentioning only itself does not count
+        assert_eq!(
+            unreferenced_files(root).unwrap(),
+            [root.join("scripts/orphan.sh")]
+        );
+    }
+}
@@ -1,10 +1,20 @@
 mod commands;
+mod diagnostics;
+mod frontmatter;
+mod hooks;
+mod json;
+mod kits;
+mod lint;
+mod manifest;
+mod mcp;
 mod templates;
 mod utils;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use commands::{add, init}; // Only import used commands
+use commands::{add, bump, hook, init, remove, rename};
+use std::io::IsTerminal;
+use std::process::ExitCode; // Only import used commands
 
 #[derive(Parser)]
 #[command(name = "cforge")]
@@ -12,6 +22,10 @@ use commands::{add, init}; // Only import used commands
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Never prompt; fail with the missing flag instead (implied when stdin is not a terminal)
+    #[arg(long, global = true)]
+    no_input: bool,
 }
 
 #[derive(Subcommand)]
@@ -29,6 +43,13 @@ enum Commands {
         name: Option<String>,
         #[arg(short, long)]
         description: Option<String>,
+        /// Starter kit: minimal, commands-only, hook-guard, mcp-wrapper, full,
+        /// or a kit directory or git URL
+        #[arg(short, long, value_name = "KIT")]
+        template: Option<String>,
+        /// Don't add the plugin to the enclosing marketplace.json
+        #[arg(long)]
+        no_register: bool,
     },
     /// Add a component (Skill, Command, etc) to a plugin
     Add {
@@ -39,6 +60,57 @@ enum Commands {
         #[command(subcommand)]
         component: add::Component,
     },
+    /// Exercise a plugin's hooks without starting a session
+    Hook {
+        /// Target a specific plugin by name (optional)
+        #[arg(short, long)]
+        plugin: Option<String>,
+
+        #[command(subcommand)]
+        action: hook::Action,
+    },
+    /// Remove a plugin or one of its components
+    Remove {
+        /// Target a specific plugin by name (optional)
+        #[arg(short, long)]
+        plugin: Option<String>,
+
+        /// Show what would be deleted or rewritten, without changing anything
+        #[arg(long, global = true)]
+        dry_run: bool,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long, global = true)]
+        yes: bool,
+
+        #[command(subcommand)]
+        target: remove::Target,
+    },
+    /// Rename a plugin or component and update every reference to it
+    Rename {
+        /// Target a specific plugin by name (optional)
+        #[arg(short, long)]
+        plugin: Option<String>,
+
+        /// Show what would be renamed or rewritten, without changing anything
+        #[arg(long, global = true)]
+        dry_run: bool,
+
+        #[command(subcommand)]
+        target: rename::Target,
+    },
+    /// Bump a plugin's version in plugin.json, marketplace.json and CHANGELOG.md
+    Bump {
+        /// Plugin name
+        plugin: String,
+
+        /// major, minor, patch or an explicit x.y.z
+        level: bump::Level,
+
+        /// Fill the changelog section from git commits touching the plugin
+        #[arg(long)]
+        from_git: bool,
+    },
     /// Validate a plugin or marketplace structure and syntax
     Validate {
         /// Path to plugin or marketplace (defaults to current dir)
@@ -48,12 +120,42 @@ enum Commands {
         /// Stop validation at first error
         #[arg(long)]
         fail_fast: bool,
+
+        /// Output format for diagnostics
+        #[arg(long, value_enum, default_value = "text")]
+        format: commands::validate::Format,
+
+        /// Fail on leftover scaffold placeholders (author, instructions, no-op hooks)
+        #[arg(long)]
+        strict: bool,
     },
 
     /// Register a local plugin into the marketplace.json registry
     Register {
-        /// Relative path to the plugin folder (e.g. ./plugins/my-tool)
-        path: String,
+        /// Relative path to the plugin folder (e.g. ./plugins/my-tool); with --all, a glob
+        #[arg(required_unless_present = "all")]
+        path: Option<String>,
+
+        /// Re-sync an already registered entry from the plugin's plugin.json
+        #[arg(long)]
+        update: bool,
+
+        /// Register every plugin under plugins/ (or matching PATH) and report stale entries
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Compare marketplace entries with their plugins' plugin.json files
+    Sync {
+        /// Rewrite drifted entries from the plugins' manifests
+        #[arg(long)]
+        write: bool,
+    },
+
+    /// Inspect and customize the templates cforge scaffolds from
+    Templates {
+        #[command(subcommand)]
+        action: commands::templates::Action,
     },
 
     /// List plugins in marketplace or components in plugin
@@ -65,20 +167,66 @@ enum Commands {
         /// Show verbose output (description, path, status)
         #[arg(short, long)]
         verbose: bool,
+
+        /// Output format; json, yaml and csv emit full, untruncated records
+        #[arg(long, value_enum, default_value = "table")]
+        format: commands::list::Format,
     },
 }
 
-fn main() -> Result<()> {
+fn main() -> Result<ExitCode> {
     let cli = Cli::parse();
+    if cli.no_input {
+        utils::disable_prompts("--no-input was given");
+    } else if !std::io::stdin().is_terminal() {
+        utils::disable_prompts("stdin is not a terminal");
+    }
 
     match cli.command {
         Commands::Init { name, description } => init::init_marketplace(name, description)?,
-        Commands::NewPlugin { name, description } => init::init_plugin(name, description)?,
+        Commands::NewPlugin {
+            name,
+            description,
+            template,
+            no_register,
+        } => init::init_plugin(name, description, template, !no_register)?,
         Commands::Add { plugin, component } => add::run(plugin, component)?,
-        Commands::Validate { path, fail_fast } => commands::validate::validate(path, fail_fast)?,
-        Commands::Register { path } => commands::register::register_plugin(path)?,
-        Commands::List { path, verbose } => commands::list::list(path, verbose)?,
+        Commands::Bump {
+            plugin,
+            level,
+            from_git,
+        } => bump::run(plugin, level, from_git)?,
+        Commands::Hook { plugin, action } => hook::run(plugin, action)?,
+        Commands::Remove {
+            plugin,
+            dry_run,
+            yes,
+            target,
+        } => remove::run(plugin, target, dry_run, yes)?,
+        Commands::Rename {
+            plugin,
+            dry_run,
+            target,
+        } => rename::run(plugin, target, dry_run)?,
+        Commands::Validate {
+            path,
+            fail_fast,
+            format,
+            strict,
+        } => return Ok(commands::validate::run(path, fail_fast, format, strict)),
+        Commands::Register { path, update, all } => match (path, all) {
+            (pattern, true) => commands::register::register_all(pattern, update)?,
+            (Some(path), false) => commands::register::register_plugin(path, update)?,
+            (None, false) => unreachable!("clap requires PATH without --all"),
+        },
+        Commands::Sync { write } => commands::sync::run(write)?,
+        Commands::Templates { action } => commands::templates::run(action)?,
+        Commands::List {
+            path,
+            verbose,
+            format,
+        } => commands::list::list(path, verbose, format)?,
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
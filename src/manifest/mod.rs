@@ -0,0 +1,919 @@
+use crate::json;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How `PluginManifest::check` validates a plugin.json field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Shape {
+    /// Required kebab-case string.
+    Name,
+    /// Semantic version string.
+    Version,
+    Text,
+    Author,
+    Strings,
+    /// A path or an array of paths.
+    Paths,
+    /// A path or an inline object.
+    Config,
+}
+
+/// A plugin.json field. Mirrored fields are copied into the plugin's
+/// marketplace entry by `register`/`sync` and compared for drift.
+pub struct Field {
+    pub key: &'static str,
+    shape: Shape,
+    pub mirrored: bool,
+}
+
+const fn field(key: &'static str, shape: Shape, mirrored: bool) -> Field {
+    Field {
+        key,
+        shape,
+        mirrored,
+    }
+}
+
+/// Every key accepted in `.claude-plugin/plugin.json`. Checks, drift and sync
+/// follow this table; the typed structs below must agree with it.
+pub const PLUGIN_FIELDS: &[Field] = &[
+    field("name", Shape::Name, true),
+    field("version", Shape::Version, true),
+    field("description", Shape::Text, true),
+    field("author", Shape::Author, true),
+    field("homepage", Shape::Text, true),
+    field("repository", Shape::Text, true),
+    field("license", Shape::Text, true),
+    field("keywords", Shape::Strings, true),
+    field("commands", Shape::Paths, false),
+    field("agents", Shape::Paths, false),
+    field("hooks", Shape::Config, false),
+    field("mcpServers", Shape::Config, false),
+];
+
+/// Typed view of `.claude-plugin/plugin.json`. Fields cforge does not model
+/// are kept in `extra` so a load/save round-trip preserves them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginManifest {
+    pub name: String,
+    #[serde(flatten)]
+    pub meta: Metadata,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commands: Option<PathList>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agents: Option<PathList>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<PathOrInline>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_servers: Option<PathOrInline>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The mirrored fields of `PLUGIN_FIELDS` besides `name`, shared by
+/// plugin.json and its marketplace entry.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Metadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<Author>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Author {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Typed view of `.claude-plugin/marketplace.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Marketplace {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<Owner>,
+    pub plugins: Vec<PluginEntry>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Owner {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A plugin's entry in marketplace.json. Metadata mirrors the plugin's own
+/// plugin.json; marketplace-only keys (category, strict, ...) live in `extra`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginEntry {
+    pub name: String,
+    #[serde(flatten)]
+    pub meta: Metadata,
+    pub source: PluginSource,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A mirrored field whose marketplace copy differs from plugin.json.
+/// Absent values are `null`.
+#[derive(Debug, Clone)]
+pub struct Drift {
+    pub field: &'static str,
+    pub entry: Value,
+    pub plugin: Value,
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |v: &Value| match v {
+            Value::Null => "unset".to_string(),
+            v => v.to_string(),
+        };
+        write!(
+            f,
+            "`{}` is {} in marketplace.json but {} in plugin.json",
+            self.field,
+            show(&self.entry),
+            show(&self.plugin)
+        )
+    }
+}
+
+/// Where a marketplace entry's plugin lives.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawSource", into = "RawSource")]
+pub enum PluginSource {
+    /// `"./plugins/my-tool"`, relative to the marketplace root.
+    Local(String),
+    /// `"https://host/org/repo.git"` or `{"source": "url", "url": ...}`.
+    Git(RemoteRepo),
+    /// `{"source": "github", "repo": "org/repo"}`.
+    GitHub(RemoteRepo),
+}
+
+/// A remote repository, optionally pinned to a ref and a sub-directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteRepo {
+    /// Clone URL for `Git`, `owner/name` for `GitHub`.
+    pub location: String,
+    pub git_ref: Option<String>,
+    pub path: Option<String>,
+}
+
+impl PluginSource {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PluginSource::Local(_) => "local",
+            PluginSource::Git(_) => "git",
+            PluginSource::GitHub(_) => "github",
+        }
+    }
+
+    /// Directory of a local source, resolved against the marketplace root.
+    pub fn local_path(&self, root: &Path) -> Option<PathBuf> {
+        match self {
+            PluginSource::Local(p) => Some(root.join(p)),
+            _ => None,
+        }
+    }
+
+    /// Syntax problems with a remote source; local sources are checked on disk instead.
+    pub fn check_remote(&self) -> Vec<String> {
+        let (repo, mut problems) = match self {
+            PluginSource::Local(_) => return Vec::new(),
+            PluginSource::Git(repo) => (repo, check_git_url(&repo.location)),
+            PluginSource::GitHub(repo) => (repo, check_github_repo(&repo.location)),
+        };
+        if repo.git_ref.as_deref().is_some_and(|r| r.trim().is_empty()) {
+            problems.push("`ref` is empty".to_string());
+        }
+        if let Some(path) = &repo.path {
+            if path.starts_with('/') || path.split('/').any(|seg| seg == "..") {
+                problems.push(format!("`path` \"{path}\" must stay inside the repository"));
+            }
+        }
+        problems
+    }
+}
+
+impl fmt::Display for PluginSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let repo = match self {
+            PluginSource::Local(p) => return write!(f, "{p}"),
+            PluginSource::Git(repo) => {
+                write!(f, "{}", repo.location)?;
+                repo
+            }
+            PluginSource::GitHub(repo) => {
+                write!(f, "github:{}", repo.location)?;
+                repo
+            }
+        };
+        if let Some(r) = &repo.git_ref {
+            write!(f, "@{r}")?;
+        }
+        if let Some(p) = &repo.path {
+            write!(f, "#{p}")?;
+        }
+        Ok(())
+    }
+}
+
+// On-disk shapes of `source`
+#[derive(Serialize)]
+#[serde(untagged)]
+enum RawSource {
+    String(String),
+    Object(RawSourceObject),
+}
+
+// Picks the shape by JSON type, so a bad object reports what is wrong with it
+// rather than that it matched no shape
+impl<'de> Deserialize<'de> for RawSource {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        match Value::deserialize(deserializer)? {
+            Value::String(s) => Ok(RawSource::String(s)),
+            object @ Value::Object(_) => RawSourceObject::deserialize(object)
+                .map(RawSource::Object)
+                .map_err(|e| D::Error::custom(format!("invalid `source`: {e}"))),
+            _ => Err(D::Error::custom(
+                "invalid `source`: expected a path, a git URL or an object with a \"source\" key",
+            )),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "lowercase")]
+enum RawSourceObject {
+    Github {
+        repo: String,
+        #[serde(rename = "ref", default, skip_serializing_if = "Option::is_none")]
+        git_ref: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+    },
+    #[serde(alias = "git")]
+    Url {
+        url: String,
+        #[serde(rename = "ref", default, skip_serializing_if = "Option::is_none")]
+        git_ref: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+    },
+}
+
+impl TryFrom<RawSource> for PluginSource {
+    type Error = String;
+
+    fn try_from(raw: RawSource) -> std::result::Result<Self, Self::Error> {
+        Ok(match raw {
+            RawSource::String(s) if s.trim().is_empty() => {
+                return Err("`source` must not be empty".to_string())
+            }
+            RawSource::String(s) if looks_like_git_url(&s) => PluginSource::Git(RemoteRepo {
+                location: s,
+                git_ref: None,
+                path: None,
+            }),
+            RawSource::String(s) => PluginSource::Local(s),
+            RawSource::Object(RawSourceObject::Github {
+                repo,
+                git_ref,
+                path,
+            }) => PluginSource::GitHub(RemoteRepo {
+                location: repo,
+                git_ref,
+                path,
+            }),
+            RawSource::Object(RawSourceObject::Url { url, git_ref, path }) => {
+                PluginSource::Git(RemoteRepo {
+                    location: url,
+                    git_ref,
+                    path,
+                })
+            }
+        })
+    }
+}
+
+impl From<PluginSource> for RawSource {
+    fn from(source: PluginSource) -> Self {
+        match source {
+            PluginSource::Local(p) => RawSource::String(p),
+            PluginSource::Git(RemoteRepo {
+                location,
+                git_ref: None,
+                path: None,
+            }) => RawSource::String(location),
+            PluginSource::Git(repo) => RawSource::Object(RawSourceObject::Url {
+                url: repo.location,
+                git_ref: repo.git_ref,
+                path: repo.path,
+            }),
+            PluginSource::GitHub(repo) => RawSource::Object(RawSourceObject::Github {
+                repo: repo.location,
+                git_ref: repo.git_ref,
+                path: repo.path,
+            }),
+        }
+    }
+}
+
+fn looks_like_git_url(s: &str) -> bool {
+    ["https://", "http://", "ssh://", "git://", "git@"]
+        .iter()
+        .any(|prefix| s.starts_with(prefix))
+        || s.ends_with(".git")
+}
+
+fn check_git_url(url: &str) -> Vec<String> {
+    let rest = ["https://", "http://", "ssh://", "git://"]
+        .iter()
+        .find_map(|scheme| url.strip_prefix(scheme));
+    let valid = match (rest, url.strip_prefix("file://")) {
+        // file:///path has no host
+        (_, Some(path)) => path.len() > 1 && path.starts_with('/'),
+        // scheme://host/path
+        (Some(rest), _) => rest
+            .split_once('/')
+            .is_some_and(|(host, path)| !host.is_empty() && !path.is_empty()),
+        // scp-like user@host:path
+        (None, None) => url
+            .split_once(':')
+            .is_some_and(|(host, path)| host.contains('@') && !path.is_empty()),
+    };
+    if valid && !url.contains(char::is_whitespace) {
+        Vec::new()
+    } else {
+        vec![format!("\"{url}\" is not a valid git URL")]
+    }
+}
+
+fn check_github_repo(repo: &str) -> Vec<String> {
+    let valid_part = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    match repo.split_once('/') {
+        Some((owner, name)) if valid_part(owner) && valid_part(name) => Vec::new(),
+        _ => vec![format!(
+            "\"{repo}\" is not a GitHub repository (expected owner/name)"
+        )],
+    }
+}
+
+/// Why marketplace.json could not be parsed.
+#[derive(Debug)]
+pub enum MarketplaceError {
+    /// Invalid JSON, or a problem outside the plugin entries.
+    Syntax(serde_json::Error),
+    /// A plugin entry that does not match the schema, at `/plugins/<index>`.
+    Entry { pointer: String, message: String },
+}
+
+impl fmt::Display for MarketplaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarketplaceError::Syntax(e) => write!(f, "{e}"),
+            MarketplaceError::Entry { pointer, message } => write!(f, "{pointer}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for MarketplaceError {}
+
+impl Marketplace {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Failed to read {path:?}"))?;
+        Self::parse(&content).with_context(|| format!("Invalid marketplace.json {path:?}"))
+    }
+
+    /// Parses marketplace.json, checking each plugin entry on its own so an
+    /// error names the entry it is in.
+    pub fn parse(content: &str) -> std::result::Result<Self, MarketplaceError> {
+        let value: Value = serde_json::from_str(content).map_err(MarketplaceError::Syntax)?;
+        let entries = value.get("plugins").and_then(Value::as_array);
+        for (i, entry) in entries.into_iter().flatten().enumerate() {
+            if let Err(e) = PluginEntry::deserialize(entry) {
+                let message = match entry.get("name").and_then(Value::as_str) {
+                    Some(name) => format!("plugin '{name}': {e}"),
+                    None => e.to_string(),
+                };
+                return Err(MarketplaceError::Entry {
+                    pointer: format!("/plugins/{i}"),
+                    message,
+                });
+            }
+        }
+        serde_json::from_str(content).map_err(MarketplaceError::Syntax)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        json::write(path, self)
+    }
+
+    pub fn find_mut(&mut self, name: &str) -> Option<&mut PluginEntry> {
+        self.plugins.iter_mut().find(|p| p.name == name)
+    }
+}
+
+impl PluginEntry {
+    pub fn new(plugin: &PluginManifest, source: PluginSource) -> Self {
+        Self {
+            name: plugin.name.clone(),
+            meta: plugin.meta.clone(),
+            source,
+            extra: Map::new(),
+        }
+    }
+
+    /// Copies the mirrored metadata from `plugin`, leaving `extra` untouched.
+    pub fn sync_from(&mut self, plugin: &PluginManifest, source: PluginSource) {
+        self.name = plugin.name.clone();
+        self.meta = plugin.meta.clone();
+        self.source = source;
+    }
+
+    /// Mirrored fields where this entry disagrees with `plugin`, in
+    /// `PLUGIN_FIELDS` order.
+    pub fn drift(&self, plugin: &PluginManifest) -> Vec<Drift> {
+        let entry = mirrored(&self.name, &self.meta);
+        let manifest = mirrored(&plugin.name, &plugin.meta);
+        PLUGIN_FIELDS
+            .iter()
+            .filter(|f| f.mirrored)
+            .filter_map(|f| {
+                let entry = entry.get(f.key).cloned().unwrap_or(Value::Null);
+                let plugin = manifest.get(f.key).cloned().unwrap_or(Value::Null);
+                (entry != plugin).then_some(Drift {
+                    field: f.key,
+                    entry,
+                    plugin,
+                })
+            })
+            .collect()
+    }
+}
+
+// The mirrored fields as JSON, keyed like plugin.json
+fn mirrored(name: &str, meta: &Metadata) -> Map<String, Value> {
+    let mut fields = match serde_json::to_value(meta) {
+        Ok(Value::Object(fields)) => fields,
+        _ => Map::new(),
+    };
+    fields.insert("name".to_string(), Value::String(name.to_string()));
+    fields
+}
+
+impl PluginManifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Failed to read {path:?}"))?;
+        serde_json::from_str(&content).with_context(|| format!("Invalid plugin.json {path:?}"))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        json::write(path, self)
+    }
+}
+
+/// A single path or a list of paths, relative to the plugin root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PathList {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Either a path to a config file or the config inlined as an object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PathOrInline {
+    Path(String),
+    Inline(Map<String, Value>),
+}
+
+/// A single rule violation, located by JSON pointer.
+#[derive(Debug)]
+pub struct Violation {
+    /// Diagnostic rule id, `manifest-schema` or `unknown-field`.
+    pub rule: &'static str,
+    pub pointer: String,
+    pub message: String,
+}
+
+impl Violation {
+    fn new(pointer: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            rule: "manifest-schema",
+            pointer: pointer.into(),
+            message: message.into(),
+        }
+    }
+
+    fn unknown(pointer: String) -> Self {
+        Self {
+            rule: "unknown-field",
+            ..Self::new(pointer, "unknown field")
+        }
+    }
+}
+
+impl PathList {
+    fn entries(&self) -> Vec<(String, &str)> {
+        match self {
+            PathList::One(p) => vec![(String::new(), p.as_str())],
+            PathList::Many(ps) => ps
+                .iter()
+                .enumerate()
+                .map(|(i, p)| (format!("/{i}"), p.as_str()))
+                .collect(),
+        }
+    }
+}
+
+impl PluginManifest {
+    /// Checks a parsed plugin.json against the field rules. Paths are
+    /// resolved against `root` and must exist.
+    pub fn check(value: &Value, root: &Path) -> Vec<Violation> {
+        let mut out = Vec::new();
+
+        let Some(obj) = value.as_object() else {
+            out.push(Violation::new("", "manifest must be a JSON object"));
+            return out;
+        };
+
+        for key in obj.keys() {
+            if !PLUGIN_FIELDS.iter().any(|f| f.key == key) {
+                out.push(Violation::unknown(pointer(&[key])));
+            }
+        }
+
+        for field in PLUGIN_FIELDS {
+            let ptr = pointer(&[field.key]);
+            match obj.get(field.key) {
+                Some(value) => check_field(field.shape, value, &ptr, &mut out),
+                None if field.shape == Shape::Name => {
+                    out.push(Violation::new(ptr, "required field is missing"))
+                }
+                None => {}
+            }
+        }
+
+        // Path existence is only meaningful once the shape is right.
+        if out.is_empty() {
+            match serde_json::from_value::<PluginManifest>(value.clone()) {
+                Ok(manifest) => manifest.check_paths(root, &mut out),
+                Err(e) => out.push(Violation::new("", e.to_string())),
+            }
+        }
+
+        out
+    }
+
+    fn check_paths(&self, root: &Path, out: &mut Vec<Violation>) {
+        let mut paths = Vec::new();
+        for (key, list) in [("/commands", &self.commands), ("/agents", &self.agents)] {
+            if let Some(list) = list {
+                for (suffix, p) in list.entries() {
+                    paths.push((format!("{key}{suffix}"), p));
+                }
+            }
+        }
+        for (key, spec) in [("/hooks", &self.hooks), ("/mcpServers", &self.mcp_servers)] {
+            if let Some(PathOrInline::Path(p)) = spec {
+                paths.push((key.to_string(), p.as_str()));
+            }
+        }
+
+        for (ptr, p) in paths {
+            if !p.starts_with("./") {
+                out.push(Violation::new(
+                    ptr,
+                    format!("\"{p}\" must be relative to the plugin root and start with ./"),
+                ));
+            } else if !root.join(p).exists() {
+                out.push(Violation::new(ptr, format!("\"{p}\" does not exist")));
+            }
+        }
+    }
+}
+
+fn check_field(shape: Shape, value: &Value, ptr: &str, out: &mut Vec<Violation>) {
+    match (shape, value) {
+        (Shape::Name, Value::String(name)) if !is_kebab_case(name) => out.push(Violation::new(
+            ptr,
+            format!("\"{name}\" is not kebab-case (e.g. my-plugin)"),
+        )),
+        (Shape::Version, Value::String(v)) => {
+            if let Err(e) = semver::Version::parse(v) {
+                out.push(Violation::new(
+                    ptr,
+                    format!("\"{v}\" is not a semantic version ({e})"),
+                ));
+            }
+        }
+        (Shape::Name | Shape::Text, Value::String(_)) => {}
+        (Shape::Name | Shape::Version | Shape::Text, _) => {
+            out.push(Violation::new(ptr, "expected a string"))
+        }
+        (Shape::Author, author) => check_author(author, out),
+        (Shape::Strings, items) => check_string_array(items, ptr, out),
+        (Shape::Paths, Value::String(_)) => {}
+        (Shape::Paths, v @ Value::Array(_)) => check_string_array(v, ptr, out),
+        (Shape::Paths, _) => out.push(Violation::new(ptr, "expected a path or an array of paths")),
+        (Shape::Config, Value::String(_) | Value::Object(_)) => {}
+        (Shape::Config, _) => out.push(Violation::new(ptr, "expected a path or an inline object")),
+    }
+}
+
+fn check_author(author: &Value, out: &mut Vec<Violation>) {
+    let Some(obj) = author.as_object() else {
+        out.push(Violation::new(
+            "/author",
+            "expected an object like {\"name\": \"...\", \"email\": \"...\"}",
+        ));
+        return;
+    };
+    match obj.get("name") {
+        Some(Value::String(_)) => {}
+        Some(_) => out.push(Violation::new("/author/name", "expected a string")),
+        None => out.push(Violation::new("/author/name", "required field is missing")),
+    }
+    for (key, value) in obj {
+        match key.as_str() {
+            "name" => {}
+            "email" | "url" if value.is_string() => {}
+            "email" | "url" => out.push(Violation::new(
+                pointer(&["author", key]),
+                "expected a string",
+            )),
+            _ => out.push(Violation::unknown(pointer(&["author", key]))),
+        }
+    }
+}
+
+fn check_string_array(value: &Value, ptr: &str, out: &mut Vec<Violation>) {
+    let Some(items) = value.as_array() else {
+        out.push(Violation::new(ptr, "expected an array of strings"));
+        return;
+    };
+    for (i, item) in items.iter().enumerate() {
+        if !item.is_string() {
+            out.push(Violation::new(format!("{ptr}/{i}"), "expected a string"));
+        }
+    }
+}
+
+/// Builds a JSON pointer (RFC 6901) from unescaped path segments.
+pub fn pointer(segments: &[&str]) -> String {
+    segments
+        .iter()
+        .map(|s| format!("/{}", s.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+pub fn is_kebab_case(s: &str) -> bool {
+    !s.is_empty()
+        && s.split('-').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn source(value: Value) -> PluginSource {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn remote(location: &str, git_ref: Option<&str>, path: Option<&str>) -> RemoteRepo {
+        RemoteRepo {
+            location: location.to_string(),
+            git_ref: git_ref.map(str::to_string),
+            path: path.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn typed_structs_agree_with_the_field_table() {
+        let manifest: PluginManifest = serde_json::from_value(json!({
+            "name": "x",
+            "version": "1.0.0",
+            "description": "d",
+            "author": {"name": "a"},
+            "homepage": "h",
+            "repository": "r",
+            "license": "MIT",
+            "keywords": ["k"],
+            "commands": "./c",
+            "agents": "./a",
+            "hooks": "./h.json",
+            "mcpServers": "./.mcp.json",
+        }))
+        .unwrap();
+        assert!(manifest.extra.is_empty());
+
+        let keys: Vec<_> = PLUGIN_FIELDS.iter().map(|f| f.key).collect();
+        let Value::Object(serialized) = serde_json::to_value(&manifest).unwrap() else {
+            panic!("manifest did not serialize to an object");
+        };
+        assert_eq!(serialized.keys().collect::<Vec<_>>(), keys);
+
+        // Every mirrored field, and only those, lives in `Metadata` (or is the name)
+        let mut expected: Vec<_> = PLUGIN_FIELDS
+            .iter()
+            .filter(|f| f.mirrored)
+            .map(|f| f.key)
+            .collect();
+        let mut found: Vec<_> = mirrored(&manifest.name, &manifest.meta)
+            .keys()
+            .cloned()
+            .collect();
+        expected.sort();
+        found.sort();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn round_trips_unknown_fields() {
+        let plugin = json!({
+            "name": "x",
+            "version": "1.0.0",
+            "author": {"name": "a", "twitter": "@a"},
+            "x-internal": {"team": "infra"},
+        });
+        let manifest: PluginManifest = serde_json::from_value(plugin.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&manifest).unwrap(), plugin);
+
+        let marketplace = json!({
+            "name": "mk",
+            "owner": {"name": "o", "url": "u"},
+            "plugins": [{"name": "x", "source": "./plugins/x", "category": "dev", "strict": false}],
+            "metadata": {"v": 1},
+        });
+        let parsed = Marketplace::parse(&marketplace.to_string()).unwrap();
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), marketplace);
+    }
+
+    #[test]
+    fn parses_every_source_shape() {
+        assert_eq!(
+            source(json!("./plugins/x")),
+            PluginSource::Local("./plugins/x".into())
+        );
+        assert_eq!(
+            source(json!("https://example.com/x.git")),
+            PluginSource::Git(remote("https://example.com/x.git", None, None))
+        );
+        assert_eq!(
+            source(json!({"source": "github", "repo": "org/x", "ref": "v1", "path": "p"})),
+            PluginSource::GitHub(remote("org/x", Some("v1"), Some("p")))
+        );
+        assert_eq!(
+            source(json!({"source": "git", "url": "git@host:org/x.git"})),
+            PluginSource::Git(remote("git@host:org/x.git", None, None))
+        );
+
+        // A plain git URL is written back as a string, anything more as an object
+        for raw in [
+            json!("./plugins/x"),
+            json!("https://example.com/x.git"),
+            json!({"source": "url", "url": "https://example.com/x.git", "ref": "main"}),
+            json!({"source": "github", "repo": "org/x"}),
+        ] {
+            assert_eq!(serde_json::to_value(source(raw.clone())).unwrap(), raw);
+        }
+    }
+
+    #[test]
+    fn reports_the_entry_with_a_bad_source() {
+        let marketplace = json!({
+            "name": "mk",
+            "plugins": [
+                {"name": "ok", "source": "./plugins/ok"},
+                {"name": "bad", "source": {"source": "github"}},
+            ],
+        });
+        match Marketplace::parse(&marketplace.to_string()) {
+            Err(MarketplaceError::Entry { pointer, message }) => {
+                assert_eq!(pointer, "/plugins/1");
+                assert!(message.starts_with("plugin 'bad': invalid `source`"));
+                assert!(message.contains("missing field `repo`"), "{message}");
+            }
+            other => panic!("expected an entry error, got {other:?}"),
+        }
+        assert!(serde_json::from_value::<PluginSource>(json!("")).is_err());
+        assert!(serde_json::from_value::<PluginSource>(json!(3)).is_err());
+    }
+
+    #[test]
+    fn checks_git_urls() {
+        for url in [
+            "https://github.com/org/x.git",
+            "ssh://git@host/org/x",
+            "git@github.com:org/x.git",
+            "file:///srv/x",
+        ] {
+            assert!(check_git_url(url).is_empty(), "{url}");
+        }
+        for url in [
+            "https://",
+            "https://host",
+            "host:org/x",
+            "https://host/a b",
+            "file://",
+            "file://srv/x",
+        ] {
+            assert_eq!(check_git_url(url).len(), 1, "{url}");
+        }
+    }
+
+    #[test]
+    fn checks_github_repos() {
+        assert!(check_github_repo("org/my.repo_1").is_empty());
+        for repo in ["org", "org/", "/x", "org/x/y", "org/x y"] {
+            assert_eq!(check_github_repo(repo).len(), 1, "{repo}");
+        }
+    }
+
+    #[test]
+    fn drift_follows_the_field_table() {
+        let plugin: PluginManifest =
+            serde_json::from_value(json!({"name": "x", "version": "1.1.0", "license": "MIT"}))
+                .unwrap();
+        let mut entry = PluginEntry::new(&plugin, PluginSource::Local("./x".into()));
+        assert!(entry.drift(&plugin).is_empty());
+
+        entry.meta.version = Some("1.0.0".into());
+        entry.meta.license = None;
+        let fields: Vec<_> = entry.drift(&plugin).iter().map(|d| d.field).collect();
+        assert_eq!(fields, ["version", "license"]);
+
+        entry.sync_from(&plugin, PluginSource::Local("./x".into()));
+        assert!(entry.drift(&plugin).is_empty());
+    }
+
+    #[test]
+    fn check_reports_by_pointer() {
+        let dir = tempfile::tempdir().unwrap();
+        let value = json!({
+            "name": "Not Kebab",
+            "version": "1",
+            "keywords": ["a", 1],
+            "commands": 3,
+            "extra": true,
+        });
+        let found: Vec<_> = PluginManifest::check(&value, dir.path())
+            .into_iter()
+            .map(|v| (v.rule, v.pointer))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("unknown-field", "/extra".to_string()),
+                ("manifest-schema", "/name".to_string()),
+                ("manifest-schema", "/version".to_string()),
+                ("manifest-schema", "/keywords/1".to_string()),
+                ("manifest-schema", "/commands".to_string()),
+            ]
+        );
+        let missing = PluginManifest::check(&json!({}), dir.path());
+        assert_eq!(missing[0].pointer, "/name");
+    }
+}
@@ -0,0 +1,238 @@
+use crate::hooks::PLUGIN_ROOT_VAR;
+use crate::json;
+use crate::manifest::{pointer, Violation};
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How Claude Code talks to an MCP server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Transport {
+    Stdio,
+    Http,
+    Sse,
+}
+
+impl Transport {
+    pub fn name(self) -> &'static str {
+        match self {
+            Transport::Stdio => "stdio",
+            Transport::Http => "http",
+            Transport::Sse => "sse",
+        }
+    }
+}
+
+/// Typed view of a plugin's `.mcp.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpConfig {
+    #[serde(default)]
+    pub mcp_servers: Map<String, Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// One server entry: `command`/`args`/`env` for stdio, `url`/`headers` otherwise.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct McpServer {
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub transport: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl McpConfig {
+    pub const FILE: &'static str = ".mcp.json";
+
+    pub fn path(plugin_root: &Path) -> PathBuf {
+        plugin_root.join(Self::FILE)
+    }
+
+    /// Loads .mcp.json, or an empty config if the plugin has none yet.
+    pub fn load_or_default(plugin_root: &Path) -> Result<Self> {
+        let path = Self::path(plugin_root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content =
+            fs::read_to_string(&path).with_context(|| format!("Failed to read {path:?}"))?;
+        serde_json::from_str(&content).with_context(|| format!("Invalid .mcp.json {path:?}"))
+    }
+
+    pub fn save(&self, plugin_root: &Path) -> Result<()> {
+        json::write(&Self::path(plugin_root), self)
+    }
+
+    /// Adds a server; returns false if one with that name already exists.
+    pub fn add_server(&mut self, name: &str, server: &McpServer) -> Result<bool> {
+        if self.mcp_servers.contains_key(name) {
+            return Ok(false);
+        }
+        self.mcp_servers
+            .insert(name.to_string(), serde_json::to_value(server)?);
+        Ok(true)
+    }
+}
+
+/// Rewrites a `./relative` path to `${CLAUDE_PLUGIN_ROOT}/relative`, since
+/// servers are not started from the plugin directory.
+pub fn anchor_to_plugin_root(arg: &str) -> String {
+    match arg.strip_prefix("./") {
+        Some(rest) => format!("{PLUGIN_ROOT_VAR}/{rest}"),
+        None => arg.to_string(),
+    }
+}
+
+/// Checks a whole `.mcp.json` document.
+pub fn check_file(value: &Value, root: &Path) -> Vec<Violation> {
+    match value.get("mcpServers") {
+        Some(servers) => check_servers(servers, "/mcpServers", root),
+        None => vec![violation("", "missing `mcpServers` object")],
+    }
+}
+
+/// Checks a `{ "<name>": { ... } }` map of servers located at `base`.
+pub fn check_servers(servers: &Value, base: &str, root: &Path) -> Vec<Violation> {
+    let mut out = Vec::new();
+    let Some(servers) = servers.as_object() else {
+        out.push(violation(base, "expected an object of named servers"));
+        return out;
+    };
+
+    for (name, server) in servers {
+        let ptr = format!("{base}{}", pointer(&[name]));
+        let Some(obj) = server.as_object() else {
+            out.push(violation(&ptr, "expected a server object"));
+            continue;
+        };
+
+        let transport = match obj.get("type") {
+            None => Transport::Stdio,
+            Some(Value::String(t)) => match Transport::from_str(t, false) {
+                Ok(t) => t,
+                Err(_) => {
+                    out.push(violation(
+                        &format!("{ptr}/type"),
+                        format!("unknown transport \"{t}\" (expected stdio, http or sse)"),
+                    ));
+                    continue;
+                }
+            },
+            Some(_) => {
+                out.push(violation(&format!("{ptr}/type"), "expected a string"));
+                continue;
+            }
+        };
+
+        let (required, forbidden) = match transport {
+            Transport::Stdio => ("command", ["url", "headers"]),
+            Transport::Http | Transport::Sse => ("url", ["command", "args"]),
+        };
+        match obj.get(required) {
+            Some(Value::String(_)) => {}
+            Some(_) => out.push(violation(&format!("{ptr}/{required}"), "expected a string")),
+            None => out.push(violation(
+                &ptr,
+                format!("{} server needs `{required}`", transport.name()),
+            )),
+        }
+        for key in forbidden {
+            if obj.contains_key(key) {
+                out.push(violation(
+                    &format!("{ptr}/{key}"),
+                    format!("`{key}` is not used by {} servers", transport.name()),
+                ));
+            }
+        }
+
+        if let Some(Value::String(url)) = obj.get("url") {
+            let templated = url.starts_with("${");
+            if !templated && !url.starts_with("https://") && !url.starts_with("http://") {
+                out.push(violation(
+                    &format!("{ptr}/url"),
+                    format!("\"{url}\" is not an http(s) URL"),
+                ));
+            }
+        }
+
+        if let Some(args) = obj.get("args") {
+            if !args
+                .as_array()
+                .is_some_and(|a| a.iter().all(Value::is_string))
+            {
+                out.push(violation(
+                    &format!("{ptr}/args"),
+                    "expected an array of strings",
+                ));
+            }
+        }
+        for key in ["env", "headers"] {
+            if let Some(map) = obj.get(key) {
+                if !map
+                    .as_object()
+                    .is_some_and(|m| m.values().all(Value::is_string))
+                {
+                    out.push(violation(
+                        &format!("{ptr}/{key}"),
+                        "expected an object of string values",
+                    ));
+                }
+            }
+        }
+
+        // Local binaries and scripts must ship with the plugin
+        let mut local = Vec::new();
+        if let Some(Value::String(command)) = obj.get("command") {
+            local.push((format!("{ptr}/command"), command.as_str()));
+        }
+        if let Some(Value::Array(args)) = obj.get("args") {
+            for (i, arg) in args.iter().enumerate() {
+                if let Some(arg) = arg.as_str() {
+                    local.push((format!("{ptr}/args/{i}"), arg));
+                }
+            }
+        }
+        for (arg_ptr, arg) in local {
+            if let Some(rel) = arg.strip_prefix(PLUGIN_ROOT_VAR) {
+                if !root.join(rel.trim_start_matches('/')).exists() {
+                    out.push(violation(
+                        &arg_ptr,
+                        format!("{arg} does not exist in the plugin"),
+                    ));
+                }
+            } else if let Some(rest) = arg.strip_prefix("./") {
+                out.push(Violation {
+                    rule: "mcp-relative-path",
+                    pointer: arg_ptr,
+                    message: format!(
+                        "\"{arg}\" is relative to Claude's working directory; use {PLUGIN_ROOT_VAR}/{rest}"
+                    ),
+                });
+            }
+        }
+    }
+    out
+}
+
+fn violation(ptr: &str, message: impl Into<String>) -> Violation {
+    Violation {
+        rule: "mcp-config",
+        pointer: ptr.to_string(),
+        message: message.into(),
+    }
+}
@@ -1,5 +1,63 @@
-pub fn get_claude_context() -> &'static str {
-    r#"# Claude Code Marketplace Guide
+use crate::frontmatter;
+use crate::manifest::PluginManifest;
+use crate::utils;
+use anyhow::{anyhow, Context, Result};
+use minijinja::{AutoEscape, Environment};
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Scaffold text authors are expected to replace. `validate` flags any of it
+// that is still present, so the built-in templates below must contain these
+// verbatim
+pub const AUTHOR_NAME_PLACEHOLDER: &str = "Your Name";
+pub const AUTHOR_EMAIL_PLACEHOLDER: &str = "you@example.com";
+pub const INSTRUCTIONS_PLACEHOLDER: &str = "(Instructions for Claude:";
+pub const AGENT_PLACEHOLDER: &str = "You are a specialist agent. Your goal is...";
+pub const ARGUMENT_HINT_PLACEHOLDER: &str = "argument-hint: [arg1]";
+
+/// Project templates, relative to the marketplace (or plugin) root.
+pub const PROJECT_DIR: &str = ".cforge/templates";
+
+/// Built-in templates, by the file name that overrides each one.
+pub const BUILTIN: &[(&str, &str)] = &[
+    ("plugin.json", PLUGIN_JSON),
+    ("marketplace.json", MARKETPLACE_JSON),
+    ("README.md", PLUGIN_README),
+    ("marketplace-README.md", MARKETPLACE_README),
+    ("CLAUDE.md", CLAUDE_CONTEXT),
+    ("skill.md", SKILL),
+    ("legacy-skill.md", LEGACY_SKILL),
+    ("command.md", COMMAND),
+    ("agent.md", AGENT),
+    ("hook.sh", HOOK_SCRIPT),
+];
+
+const PLUGIN_JSON: &str = r#"{
+  "name": {{ name | json }},
+  "description": {{ description | json }},
+  "version": "0.1.0",
+  "author": {
+    "name": {{ author | json }},
+    "email": {{ author_email | json }}
+  }
+}
+"#;
+
+const MARKETPLACE_JSON: &str = r#"{
+  "name": {{ name | json }},
+  "description": {{ description | json }},
+  "plugins": []
+}
+"#;
+
+const PLUGIN_README: &str = "# {{ name }}";
+
+const MARKETPLACE_README: &str = "# {{ name }}
+
+{{ description }}";
+
+const CLAUDE_CONTEXT: &str = r#"# Claude Code Marketplace Guide
 
 ## 🧠 Role & Context
 You are managing a Claude Code Plugin Marketplace. Your goal is to maintain a strict, valid architecture while helping the user build useful tools.
@@ -16,68 +74,297 @@ You are managing a Claude Code Plugin Marketplace. Your goal is to maintain a st
 1. **Frontmatter (YAML)**: Located between `---`. DO NOT DELETE. Only edit `description` or `argument-hint` if logic changes.
 2. **Body**: You are free to edit the text below the second `---`.
 3. **Manifests**: Ensure `plugin.json` remains valid JSON.
-"#
-}
+"#;
 
-pub fn get_plugin_manifest(name: &str, desc: &str) -> String {
-    format!(
-        r#"{{
-  "name": "{name}",
-  "description": "{desc}",
-  "version": "0.1.0",
-  "author": {{
-    "name": "Your Name",
-    "email": "you@example.com"
-  }}
-}}"#
-    )
-}
+const SKILL: &str = r#"---
+name: {{ slug | yaml }}
+description: {{ description | yaml }}
+---
 
-pub fn get_marketplace_manifest(name: &str, desc: &str) -> String {
-    format!(
-        r#"{{
-  "name": "{name}",
-  "description": "{desc}",
-  "plugins": []
-}}"#
-    )
-}
+# {{ name }}
 
-pub fn get_skill_template(name: &str, desc: &str) -> String {
-    format!(
-        r#"---
-description: {desc}
+(Instructions for Claude: Describe when to use this skill, the steps to follow, and the expected output. Supporting files such as scripts or reference docs can live next to this SKILL.md.)"#;
+
+/// Flat `skills/<name>.md` layout used before skills moved to `skills/<name>/SKILL.md`.
+const LEGACY_SKILL: &str = r#"---
+description: {{ description | yaml }}
 argument-hint: [arg1]
 ---
 
-# {name}
+# {{ name }}
 
-(Instructions for Claude: Describe how to use this tool, what inputs it expects, and the output format.)"#
-    )
-}
+(Instructions for Claude: Describe how to use this tool, what inputs it expects, and the output format.)"#;
 
-pub fn get_command_template(name: &str, desc: &str) -> String {
-    format!(
-        r#"---
-description: {desc}
+const COMMAND: &str = r#"---
+description: {{ description | yaml }}
 ---
 
-# {name}
+# {{ name }}
 
-(Instructions for Claude: This text is injected when the user types /{name}.)"#
-    )
-}
+(Instructions for Claude: This text is injected when the user types /{{ slug }}.)"#;
 
-pub fn get_agent_template(name: &str, desc: &str, capabilities: &str) -> String {
-    format!(
-        r#"---
-description: {desc}
-capabilities: [{capabilities}]
+const AGENT: &str = r#"---
+description: {{ description | yaml }}
+capabilities: {{ capabilities | yaml }}
 ---
 
-# {name} Agent
+# {{ name }} Agent
 
 You are a specialist agent. Your goal is...
-"#
-    )
+"#;
+
+const HOOK_SCRIPT: &str = r#"#!/bin/bash
+# {{ event }} hook. Claude Code passes the event payload as JSON on stdin.
+# Exit 0 to continue; exit 2 to block (stderr is shown to Claude).
+input=$(cat)
+
+echo "{{ event }} hook triggered" >&2
+exit 0
+"#;
+
+/// Where a template's source came from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Origin {
+    Project(PathBuf),
+    User(PathBuf),
+    BuiltIn,
+}
+
+impl Origin {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Origin::Project(_) => "project",
+            Origin::User(_) => "user",
+            Origin::BuiltIn => "built-in",
+        }
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Origin::Project(p) | Origin::User(p) => Some(p),
+            Origin::BuiltIn => None,
+        }
+    }
+}
+
+/// Template lookup (project, then user, then built-in) plus the variables
+/// every template can use: `plugin`, `author`, `author_email` and `date`.
+pub struct Templates {
+    project: Option<PathBuf>,
+    user: Option<PathBuf>,
+    globals: Map<String, Value>,
+}
+
+impl Templates {
+    /// Templates for scaffolding under `start`: the nearest `.cforge/templates`
+    /// above it, then `~/.config/cforge/templates`. Inside a plugin, `plugin`
+    /// and the author come from its plugin.json.
+    pub fn discover(start: &Path) -> Self {
+        let start = start.canonicalize().unwrap_or_else(|_| start.to_path_buf());
+        let project = start
+            .ancestors()
+            .map(|dir| dir.join(PROJECT_DIR))
+            .find(|dir| dir.is_dir());
+        let mut templates = Templates {
+            project,
+            user: user_dir().filter(|dir| dir.is_dir()),
+            ..Self::builtin()
+        };
+        if let Ok(plugin) = PluginManifest::load(&start.join(".claude-plugin/plugin.json")) {
+            templates = templates.with("plugin", &plugin.name);
+            if let Some(author) = plugin.meta.author {
+                templates = templates.with("author", &author.name);
+                if let Some(email) = author.email {
+                    templates = templates.with("author_email", &email);
+                }
+            }
+        }
+        templates
+    }
+
+    /// Built-in templates only.
+    pub fn builtin() -> Self {
+        let globals = json!({
+            "plugin": "",
+            "author": AUTHOR_NAME_PLACEHOLDER,
+            "author_email": AUTHOR_EMAIL_PLACEHOLDER,
+            "date": utils::today(),
+        });
+        Templates {
+            project: None,
+            user: None,
+            globals: globals.as_object().cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Sets a variable available to every template.
+    pub fn with(mut self, key: &str, value: &str) -> Self {
+        self.globals.insert(key.to_string(), Value::from(value));
+        self
+    }
+
+    /// The effective source of template `name` and where it came from.
+    pub fn source(&self, name: &str) -> Result<(Origin, String)> {
+        let builtin = BUILTIN
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, source)| *source)
+            .ok_or_else(|| {
+                let known: Vec<_> = BUILTIN.iter().map(|(n, _)| *n).collect();
+                anyhow!("Unknown template '{name}' (known: {})", known.join(", "))
+            })?;
+        let overrides = [
+            self.project.as_ref().map(|d| Origin::Project(d.join(name))),
+            self.user.as_ref().map(|d| Origin::User(d.join(name))),
+        ];
+        for origin in overrides.into_iter().flatten() {
+            let path = origin.path().unwrap();
+            if path.is_file() {
+                let source = fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read template {path:?}"))?;
+                return Ok((origin, source));
+            }
+        }
+        Ok((Origin::BuiltIn, builtin.to_string()))
+    }
+
+    /// Renders template `name` with `vars` on top of the globals.
+    pub fn render(&self, name: &str, vars: Value) -> Result<String> {
+        let (origin, source) = self.source(name)?;
+        let file = match origin.path() {
+            Some(path) => path.display().to_string(),
+            None => format!("built-in {name}"),
+        };
+        self.render_str(&file, &source, vars)
+    }
+
+    /// Renders `source`, read from `file`, checking that a `.json` file's
+    /// output parses and that any frontmatter is valid YAML.
+    pub fn render_str(&self, file: &str, source: &str, vars: Value) -> Result<String> {
+        let mut context = self.globals.clone();
+        if let Value::Object(vars) = vars {
+            context.extend(vars);
+        }
+        let env = environment();
+        let output = env
+            .template_from_named_str(file, source)
+            .and_then(|t| t.render(Value::Object(context)))
+            .with_context(|| format!("Failed to render {file}"))?;
+
+        if file.ends_with(".json") {
+            serde_json::from_str::<Value>(&output)
+                .with_context(|| format!("{file} rendered invalid JSON"))?;
+        }
+        if output.starts_with("---") {
+            frontmatter::parse(&output)
+                .map_err(|e| anyhow!("{file} rendered invalid frontmatter: {}", e.message))?;
+        }
+        Ok(output)
+    }
+
+    pub fn plugin_manifest(&self, name: &str, desc: &str) -> Result<String> {
+        self.render("plugin.json", json!({ "name": name, "description": desc }))
+    }
+
+    pub fn marketplace_manifest(&self, name: &str, desc: &str) -> Result<String> {
+        self.render(
+            "marketplace.json",
+            json!({ "name": name, "description": desc }),
+        )
+    }
+
+    pub fn plugin_readme(&self, name: &str, desc: &str) -> Result<String> {
+        self.render("README.md", json!({ "name": name, "description": desc }))
+    }
+
+    pub fn marketplace_readme(&self, name: &str, desc: &str) -> Result<String> {
+        let vars = json!({ "name": name, "description": desc });
+        self.render("marketplace-README.md", vars)
+    }
+
+    pub fn claude_context(&self) -> Result<String> {
+        self.render("CLAUDE.md", json!({}))
+    }
+
+    pub fn skill(&self, name: &str, slug: &str, desc: &str) -> Result<String> {
+        self.render("skill.md", component(name, slug, desc))
+    }
+
+    pub fn legacy_skill(&self, name: &str, slug: &str, desc: &str) -> Result<String> {
+        self.render("legacy-skill.md", component(name, slug, desc))
+    }
+
+    pub fn command(&self, name: &str, slug: &str, desc: &str) -> Result<String> {
+        self.render("command.md", component(name, slug, desc))
+    }
+
+    /// `capabilities` is a comma-separated list, e.g. "bash, search".
+    pub fn agent(&self, name: &str, slug: &str, desc: &str, capabilities: &str) -> Result<String> {
+        let mut vars = component(name, slug, desc);
+        vars["capabilities"] = capabilities
+            .split(',')
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .collect();
+        self.render("agent.md", vars)
+    }
+
+    pub fn hook_script(&self, event: &str) -> Result<String> {
+        self.render("hook.sh", json!({ "event": event }))
+    }
+}
+
+/// `~/.config/cforge/templates` (or under `$XDG_CONFIG_HOME`).
+pub fn user_dir() -> Option<PathBuf> {
+    let config = match std::env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(config.join("cforge/templates"))
+}
+
+// Names appear in Markdown headings, which end at the first newline
+fn component(name: &str, slug: &str, desc: &str) -> Value {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    json!({ "name": name, "slug": slug, "description": desc })
+}
+
+// Nothing is escaped implicitly; templates quote values with the `json` and
+// `yaml` filters where the format needs it
+fn environment<'a>() -> Environment<'a> {
+    let mut env = Environment::new();
+    env.set_keep_trailing_newline(true);
+    env.set_auto_escape_callback(|_| AutoEscape::None);
+    env.add_filter("json", |value: minijinja::Value| {
+        serde_json::to_string(&value).map_err(filter_error)
+    });
+    env.add_filter("yaml", |value: minijinja::Value| {
+        yaml_scalar(&serde_json::to_value(&value).map_err(filter_error)?)
+    });
+    env
+}
+
+// A value that can follow `key: ` on one line. serde_yaml quotes strings YAML
+// would misread (`: `, a leading `[`, "yes", ...); multi-line strings and
+// lists fall back to JSON, which YAML also accepts
+fn yaml_scalar(value: &Value) -> Result<String, minijinja::Error> {
+    let json = |v: &Value| serde_json::to_string(v).map_err(filter_error);
+    match value {
+        Value::String(s) => {
+            let yaml = serde_yaml::to_string(s).map_err(filter_error)?;
+            match yaml.trim_end_matches('\n') {
+                line if !line.contains('\n') => Ok(line.to_string()),
+                _ => json(value),
+            }
+        }
+        Value::Array(items) => {
+            let items: Result<Vec<_>, _> = items.iter().map(json).collect();
+            Ok(format!("[{}]", items?.join(", ")))
+        }
+        other => json(other),
+    }
+}
+
+fn filter_error(e: impl std::fmt::Display) -> minijinja::Error {
+    minijinja::Error::new(minijinja::ErrorKind::InvalidOperation, e.to_string())
 }
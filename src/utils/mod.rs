@@ -1,9 +1,13 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use colored::*;
-use dialoguer::{theme::ColorfulTheme, Select};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 use std::fs;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+
+// Why prompts are disabled, once they are
+static NO_INPUT: OnceLock<&'static str> = OnceLock::new();
 
 pub fn create_dir(path: &Path) -> Result<()> {
     if !path.exists() {
@@ -23,6 +27,85 @@ pub fn create_file(path: &Path, content: &str) -> Result<()> {
     Ok(())
 }
 
+/// Kebab-case form of `name` (e.g. "My Tool!" → "my-tool"), as plugin and
+/// component names must be. Names that already are kebab-case come back
+/// unchanged; digits never start a new word, so `oauth2-helper` stays as is.
+pub fn slug(name: &str) -> Result<String> {
+    let mut slug = String::new();
+    for c in name.chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_lowercase() || c.is_ascii_digit() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        return Err(anyhow!(
+            "Name \"{name}\" has no letters or digits to build a name from"
+        ));
+    }
+    Ok(slug.to_string())
+}
+
+/// Component and plugin names given on the command line are single path
+/// segments; anything else could reach outside the directory it names.
+pub fn check_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+        return Err(anyhow!(
+            "Invalid name \"{name}\": expected a plain name, not a path"
+        ));
+    }
+    Ok(())
+}
+
+/// Turns every prompt into an error naming the flag that answers it, for
+/// `--no-input` or when stdin is not a terminal. `reason` completes
+/// "cannot prompt because ...".
+pub fn disable_prompts(reason: &'static str) {
+    let _ = NO_INPUT.set(reason);
+}
+
+// Errors instead of prompting once prompts are disabled
+fn ensure_interactive(flag: &str) -> Result<()> {
+    match NO_INPUT.get() {
+        Some(reason) => Err(anyhow!(
+            "Missing {flag}, and cannot prompt because {reason}"
+        )),
+        None => Ok(()),
+    }
+}
+
+/// The value passed as `flag`, or a prompt for it.
+pub fn prompt(arg: Option<String>, label: &str, flag: &str) -> Result<String> {
+    match arg {
+        Some(value) => Ok(value),
+        None => {
+            ensure_interactive(flag)?;
+            Ok(Input::new().with_prompt(label).interact_text()?)
+        }
+    }
+}
+
+/// Asks `question`; `flag` (e.g. `--yes`) is what skips the question.
+pub fn confirm(question: &str, flag: &str) -> Result<bool> {
+    ensure_interactive(flag)?;
+    Ok(Confirm::new()
+        .with_prompt(question)
+        .default(false)
+        .interact()?)
+}
+
+/// The `--description` value, or a prompt for one. Blank descriptions are
+/// rejected, since `validate` would reject the scaffolded component.
+pub fn get_description(arg: Option<String>) -> Result<String> {
+    let desc = prompt(arg, "Description", "--description")?;
+    if desc.trim().is_empty() {
+        return Err(anyhow!("Description must not be empty"));
+    }
+    Ok(desc)
+}
+
 /// Locates the target plugin directory based on flags, CWD, or interactive selection.
 pub fn resolve_plugin_root(explicit_name: &Option<String>) -> Result<PathBuf> {
     let current_dir = std::env::current_dir()?;
@@ -38,6 +121,12 @@ pub fn resolve_plugin_root(explicit_name: &Option<String>) -> Result<PathBuf> {
                 return Ok(path);
             }
         }
+        let known = plugin_names(&plugins_in(&current_dir.join("plugins"))?);
+        if !known.is_empty() {
+            return Err(anyhow!(
+                "Plugin '{name}' not found. Plugins in ./plugins: {known}"
+            ));
+        }
         return Err(anyhow::anyhow!(
             "Plugin '{name}' not found. Are you in the marketplace root?"
         ));
@@ -51,17 +140,18 @@ pub fn resolve_plugin_root(explicit_name: &Option<String>) -> Result<PathBuf> {
     // STRATEGY 3: Interactive (We are in marketplace root -> Show Menu)
     let plugins_dir = current_dir.join("plugins");
     if plugins_dir.exists() {
-        let entries: Vec<PathBuf> = fs::read_dir(plugins_dir)?
-            .filter_map(|e| e.ok())
-            .map(|e| e.path())
-            .filter(|p| p.is_dir() && p.join(".claude-plugin/plugin.json").exists())
-            .collect();
-
+        let entries = plugins_in(&plugins_dir)?;
         if entries.is_empty() {
             return Err(anyhow::anyhow!(
                 "No plugins found in ./plugins. Create one first!"
             ));
         }
+        if let Some(reason) = NO_INPUT.get() {
+            return Err(anyhow!(
+                "Missing --plugin, and cannot prompt because {reason}. Plugins in ./plugins: {}",
+                plugin_names(&entries)
+            ));
+        }
 
         let selections: Vec<String> = entries
             .iter()
@@ -82,6 +172,42 @@ pub fn resolve_plugin_root(explicit_name: &Option<String>) -> Result<PathBuf> {
     ))
 }
 
+// Plugin directories directly under `dir`, in name order
+fn plugins_in(dir: &Path) -> Result<Vec<PathBuf>> {
+    Ok(sorted_entries(dir)?
+        .into_iter()
+        .filter(|p| p.is_dir() && p.join(".claude-plugin/plugin.json").exists())
+        .collect())
+}
+
+fn plugin_names(dirs: &[PathBuf]) -> String {
+    let names: Vec<_> = dirs
+        .iter()
+        .map(|p| p.file_name().unwrap_or_default().to_string_lossy())
+        .collect();
+    names.join(", ")
+}
+
+// Path of `path` relative to `base`, for reporting
+pub fn relative(path: &Path, base: &Path) -> String {
+    path.strip_prefix(base)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Directory entries in name order; a missing directory has none.
+pub fn sorted_entries(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut paths = fs::read_dir(dir)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    paths.sort();
+    Ok(paths)
+}
+
 fn find_plugin_root_upwards(start: &Path) -> Option<PathBuf> {
     let mut current = start;
     loop {
@@ -95,3 +221,58 @@ fn find_plugin_root_upwards(start: &Path) -> Option<PathBuf> {
     }
     None
 }
+
+/// Nearest directory at or above `start` holding a marketplace.json.
+pub fn find_marketplace_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(".claude-plugin/marketplace.json").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Today's UTC date as `YYYY-MM-DD`.
+pub fn today() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    // Howard Hinnant's days-to-civil conversion
+    let z = (secs / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slug_keeps_kebab_case_names() {
+        for name in ["oauth2-helper", "p2", "my-tool", "v2-api-3", "a"] {
+            assert_eq!(slug(name).unwrap(), name);
+        }
+    }
+
+    #[test]
+    fn slug_never_splits_on_digits() {
+        assert_eq!(slug("gpt4o tools").unwrap(), "gpt4o-tools");
+        assert_eq!(slug("OAuth2 Helper").unwrap(), "oauth2-helper");
+        assert_eq!(slug("Plugin 2").unwrap(), "plugin-2");
+    }
+
+    #[test]
+    fn slug_splits_on_whitespace_and_punctuation() {
+        assert_eq!(slug("My Tool!").unwrap(), "my-tool");
+        assert_eq!(slug("  snake_case.name  ").unwrap(), "snake-case-name");
+        assert_eq!(slug("a -- b").unwrap(), "a-b");
+        assert_eq!(slug("../../x").unwrap(), "x");
+        assert!(slug("!!").is_err());
+        assert!(slug("").is_err());
+    }
+}